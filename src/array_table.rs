@@ -1,28 +1,41 @@
 use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::{HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::mem;
-use std::ops::Sub;
+use std::ops::{RangeInclusive, Sub};
 use std::string::ToString;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use egui::{Align, Context, CursorIcon, Id, Key, Label, Sense, Style, TextEdit, Ui, Vec2, Widget, WidgetText};
+use egui::{Align, Button, Color32, Context, CursorIcon, Event, Id, Key, KeyboardShortcut, Label, Modifiers, Rangef, Rect, Sense, Stroke, Style, TextEdit, TextFormat, Ui, Vec2, Widget, WidgetText};
+use egui::text::LayoutJob;
 use egui::scroll_area::ScrollBarVisibility;
 use egui::style::Spacing;
 use egui::util::cache;
 use indexmap::IndexSet;
+use regex::Regex;
 use json_flat_parser::{FlatJsonValue, JsonArrayEntries, JSONParser, ParseOptions, ParseResult, PointerKey, ValueType};
 use json_flat_parser::serializer::serialize_to_json_with_option;
 
 
-use crate::{ACTIVE_COLOR, ArrayResponse, concat_string};
+use crate::{ACTIVE_COLOR, ArrayResponse, concat_string, log};
 use crate::components::icon;
 use crate::components::popover::PopupMenu;
+use crate::column_conversion::{Conversion, ConversionReport};
+use crate::column_profile_window::{ColumnProfile, ColumnProfileWindow, value_type_name};
+use crate::column_chooser_window::{ColumnChooserAction, ColumnChooserWindow, DraggedColumn};
 use crate::components::table::{TableBody, TableRow};
-use crate::fonts::{FILTER, THUMBTACK};
-use crate::parser::{search_occurrences};
+use crate::components::chips::{chips, CHIPS_MAX_JSON_LEN, inline_items};
+use crate::components::value_editor::{array_popup_editor, EditOutcome, popup_editor};
+use crate::find_replace_window::{FindReplaceAction, FindReplaceWindow};
+use crate::fonts::{FILTER, THUMBTACK, TRIANGLE_EXCLAMATION};
+use crate::parser::{infer_value_type, json_value, primitive_array_items, primitive_text, search_occurrences, search_regex, SearchOptions};
+use crate::schema::{SchemaValidator, value_type_for, ValueEditor, Violation};
+use crate::raw_json_view::RawJsonView;
+use crate::record_view::RecordView;
 use crate::subtable_window::SubTable;
+use crate::tree_view::{TreeAction, TreeView};
+use crate::unnest_window::{UnnestedEdit, UnnestWindow};
 
 #[derive(Clone, Debug)]
 pub struct Column {
@@ -31,6 +44,8 @@ pub struct Column {
     pub value_type: ValueType,
     pub seen_count: usize,
     pub order: usize,
+    // All value types seen in this column, value_type being the first non null one
+    pub observed_types: Vec<ValueType>,
 }
 
 impl Hash for Column {
@@ -47,8 +62,20 @@ impl Column {
             value_type,
             seen_count: 0,
             order: 0,
+            observed_types: vec![],
         }
     }
+
+    pub fn add_observed_type(&mut self, value_type: ValueType) {
+        if !self.observed_types.iter().any(|observed_type| mem::discriminant(observed_type) == mem::discriminant(&value_type)) {
+            self.observed_types.push(value_type);
+        }
+    }
+
+    /// Column has values of more than one type, null aside.
+    pub fn has_mixed_types(&self) -> bool {
+        self.observed_types.iter().filter(|value_type| !matches!(value_type, ValueType::Null)).count() > 1
+    }
 }
 
 impl Eq for Column {}
@@ -93,15 +120,60 @@ impl ScrollToRowMode {
     }
 }
 
+/// What user has customized on a table, kept when document is reloaded.
+pub struct ArrayTableViewState {
+    scroll_y: f32,
+    columns_filter: HashMap<String, Vec<String>>,
+    column_pinned: Vec<Column>,
+    hidden_columns: HashSet<String>,
+    columns_order: Vec<String>,
+    collapsed_groups: HashSet<String>,
+    schema: Option<serde_json::Value>,
+    pub depth: u8,
+}
+
+/// Rectangular range of cells, between anchor and cursor (column, row) both included.
+/// Column index goes through pinned columns then selected columns, row index is position in filtered rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellSelection {
+    pub anchor: (usize, usize),
+    pub cursor: (usize, usize),
+}
+
+impl CellSelection {
+    pub fn new(cell: (usize, usize)) -> Self {
+        Self { anchor: cell, cursor: cell }
+    }
+
+    pub fn columns(&self) -> RangeInclusive<usize> {
+        self.anchor.0.min(self.cursor.0)..=self.anchor.0.max(self.cursor.0)
+    }
+
+    pub fn rows(&self) -> RangeInclusive<usize> {
+        self.anchor.1.min(self.cursor.1)..=self.anchor.1.max(self.cursor.1)
+    }
+}
+
+enum UndoEntry {
+    // Previous value of entry of row at index
+    Value(usize, FlatJsonValue<String>),
+    // Rows appended starting at index
+    AppendedRows(usize),
+}
+
 #[derive(Default)]
 pub struct ArrayTable {
     all_columns: Vec<Column>,
     column_selected: Vec<Column>,
     column_pinned: Vec<Column>,
+    // Columns hidden with column chooser
+    hidden_columns: HashSet<String>,
+    // Columns order customized by user, columns not in this list are displayed after, sorted by default order
+    columns_order: Vec<String>,
     pub max_depth: u8,
     last_parsed_max_depth: u8,
     parse_result: Option<ParseResult<String>>,
-    pub nodes: Vec<JsonArrayEntries<String>>,
+    pub nodes: Arc<Vec<JsonArrayEntries<String>>>,
     filtered_nodes: Vec<usize>,
     scroll_y: f32,
     pub columns_filter: HashMap<String, Vec<String>>,
@@ -120,13 +192,22 @@ pub struct ArrayTable {
     pub scroll_to_column: String,
     pub scroll_to_row: String,
     pub scroll_to_row_mode: ScrollToRowMode,
+    pub search_options: SearchOptions,
+    pub search_error: Option<String>,
+    search_regex: Option<Regex>,
     pub focused_cell: Option<(usize, usize, bool)>,
+    pub selection: Option<CellSelection>,
+    scroll_x: f32,
+    scroll_view_width: f32,
+    scroll_content_width: f32,
+    visible_rows_count: usize,
 
     // Handle interaction
     pub next_frame_reset_scroll: bool,
     pub changed_scroll_to_column_value: bool,
     pub changed_matching_column_selected: bool,
     pub changed_matching_row_selected: bool,
+    scroll_to_selection_cursor: bool,
 
     #[cfg(not(target_arch = "wasm32"))]
     pub changed_scroll_to_row_value: Option<Instant>,
@@ -135,6 +216,37 @@ pub struct ArrayTable {
 
     pub editing_index: RefCell<Option<(usize, usize, bool)>>,
     pub editing_value: RefCell<String>,
+
+    find_replace_window: Option<FindReplaceWindow>,
+    column_chooser_window: Option<ColumnChooserWindow>,
+    column_profile_windows: Vec<ColumnProfileWindow>,
+    unnest_windows: Vec<UnnestWindow>,
+    // Changes made during current frame, then pushed as one undo step
+    undo_step: Vec<UndoEntry>,
+    undo_stack: Vec<Vec<UndoEntry>>,
+    schema_validator: Option<SchemaValidator>,
+    // Violations of attached schema by row index
+    violations: BTreeMap<usize, Vec<Violation>>,
+    pub show_violations: bool,
+    invalid_rows_only: bool,
+    conversion_report: Option<ConversionReport>,
+    // Column widths resized or auto-fitted by user, by column name
+    column_widths: HashMap<String, f32>,
+    pub column_widths_changed: bool,
+    // Columns to auto-fit once table has been drawn
+    auto_fit_columns: Vec<String>,
+    // Top level objects whose nested columns are replaced by a single column
+    collapsed_groups: HashSet<String>,
+    pub show_record_view: bool,
+    record_view: Option<RecordView>,
+    pub show_raw_json: bool,
+    raw_json_view: Option<RawJsonView>,
+    pub show_tree: bool,
+    tree_view: Option<TreeView>,
+    // Rows changed during current frame, views derived from rows are refreshed from them
+    changed_rows: BTreeSet<usize>,
+    // Columns unnested to build this table, when it is displayed in an unnested view
+    pub(crate) unnest_path: Option<String>,
 }
 
 
@@ -142,7 +254,47 @@ impl super::View<ArrayResponse> for ArrayTable {
     fn ui(&mut self, ui: &mut egui::Ui) -> ArrayResponse {
         use egui_extras::{Size, StripBuilder};
         let mut array_response = ArrayResponse::default();
+        if !self.is_sub_table && ui.memory(|m| m.focused().is_none()) {
+            if ui.input_mut(|i| i.consume_shortcut(&UNDO_SHORTCUT)) {
+                array_response.edited_value = self.undo();
+            } else if ui.input_mut(|i| i.consume_shortcut(&FIND_REPLACE_SHORTCUT)) {
+                self.open_find_replace();
+            } else if let Some(edited_value) = self.handle_keyboard_input(ui) {
+                array_response.edited_value = Some(edited_value);
+            }
+        }
         self.windows(ui.ctx(), &mut array_response);
+        self.unnest_windows(ui.ctx(), &mut array_response);
+        self.find_replace_window(ui.ctx(), &mut array_response);
+        self.column_chooser_window(ui.ctx());
+        self.conversion_report_window(ui.ctx());
+        self.column_profile_windows.retain(|window| {
+            let mut opened = true;
+            window.show(ui.ctx(), &mut opened);
+            opened
+        });
+        if self.show_violations && self.schema_validator.is_some() {
+            egui::SidePanel::right(Id::new("schema_violations").with(&self.parent_pointer))
+                .default_width(320.0)
+                .show_inside(ui, |ui| self.violations_panel(ui));
+        }
+        if self.show_record_view && !self.is_sub_table {
+            egui::SidePanel::right(Id::new("record_view").with(&self.parent_pointer))
+                .default_width(360.0)
+                .show_inside(ui, |ui| self.record_view_panel(ui, &mut array_response));
+        }
+        if self.show_tree && !self.is_sub_table {
+            egui::SidePanel::left(Id::new("tree_view").with(&self.parent_pointer))
+                .default_width(320.0)
+                .show_inside(ui, |ui| self.tree_panel(ui, &mut array_response));
+        }
+        if self.show_raw_json && !self.is_sub_table {
+            egui::SidePanel::right(Id::new("raw_json").with(&self.parent_pointer))
+                .default_width(420.0)
+                .show_inside(ui, |ui| self.raw_json_panel(ui, &mut array_response));
+        }
+        // Cursor can also move while drawing table, e.g. tab while editing, scroll to it next frame
+        let scroll_to_selection_cursor = self.scroll_to_selection_cursor;
         StripBuilder::new(ui)
             .size(Size::remainder())
             .vertical(|mut strip| {
@@ -187,18 +339,36 @@ impl super::View<ArrayResponse> for ArrayTable {
                                 }
                             }
 
+                            if self.scroll_to_selection_cursor {
+                                scroll_to_x = self.scroll_x_to_selection_cursor().or(scroll_to_x);
+                            }
+
                             let mut scroll_area = egui::ScrollArea::horizontal();
                             if let Some(offset) = scroll_to_x {
                                 scroll_area = scroll_area.scroll_offset(Vec2 { x: offset, y: 0.0 });
                             }
-                            scroll_area.show(ui, |ui| {
+                            let scroll_area_output = scroll_area.show(ui, |ui| {
                                 array_response = array_response.union(self.table_ui(ui, false));
                             });
+                            self.scroll_x = scroll_area_output.state.offset.x;
+                            self.scroll_view_width = scroll_area_output.inner_rect.width();
+                            self.scroll_content_width = scroll_area_output.content_size.x;
                         });
                     });
                 });
             });
+        if scroll_to_selection_cursor {
+            self.scroll_to_selection_cursor = false;
+        }
         self.cache.borrow_mut().update();
+        self.end_undo_step();
+        if !self.changed_rows.is_empty() {
+            let changed_rows = mem::take(&mut self.changed_rows);
+            self.refresh_unnest_windows(&changed_rows);
+            if let Some(tree_view) = self.tree_view.as_mut() {
+                tree_view.invalidate();
+            }
+        }
         array_response
     }
 }
@@ -209,6 +379,9 @@ struct CacheFilterOptions {}
 #[derive(Default)]
 struct CacheGetPointer {}
 
+#[derive(Default)]
+struct CacheInlineItems {}
+
 #[derive(Copy, Clone)]
 struct CachePointerKey {
     pinned_column_table: bool,
@@ -235,6 +408,13 @@ impl crate::components::cache::ComputerMut<(&Column, &String), &Vec<JsonArrayEnt
                     unique_values.insert(value);
                 }
             })
+        } else if matches!(column.value_type, ValueType::Array(_)) {
+            // Filter on elements of arrays of primitives
+            nodes.iter().enumerate().filter_map(|(i, row)| {
+                ArrayTable::get_pointer_for_column(parent_pointer, &&row.entries, i, column).and_then(|entry| entry.value.as_ref()).and_then(|value| primitive_array_items(value))
+            }).flatten().for_each(|item| {
+                unique_values.insert(primitive_text(&item));
+            })
         }
         if matches!(column.value_type, ValueType::Number) {
             unique_values.sort_by(|a, b| {
@@ -264,7 +444,19 @@ impl crate::components::cache::ComputerMut<CachePointerKey, &ArrayTable, Option<
     }
 }
 
+impl crate::components::cache::ComputerMut<&str, (), Option<Vec<serde_json::Value>>> for CacheInlineItems {
+    fn compute(&mut self, value: &str, _: ()) -> Option<Vec<serde_json::Value>> {
+        inline_items(value)
+    }
+}
+
 pub const NON_NULL_FILTER_VALUE: &str = "__non_null";
+const SEARCH_MATCH_COLOR: Color32 = Color32::from_rgb(255, 165, 0);
+const INVALID_CELL_COLOR: Color32 = Color32::from_rgba_premultiplied(90, 0, 0, 70);
+const UNDO_HISTORY_SIZE: usize = 100;
+const AUTO_FIT_WIDTH_RANGE: RangeInclusive<f32> = 40.0..=600.0;
+pub const UNDO_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+pub const FIND_REPLACE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::H);
 
 impl ArrayTable {
     pub fn new(parse_result: Option<ParseResult<String>>, nodes: Vec<JsonArrayEntries<String>>, all_columns: Vec<Column>, depth: u8, parent_pointer: String) -> Self {
@@ -274,11 +466,13 @@ impl ArrayTable {
             all_columns,
             max_depth: depth,
             filtered_nodes: (0..nodes.len()).collect::<Vec<usize>>(),
-            nodes,
+            nodes: Arc::new(nodes),
             parse_result,
             // states
             next_frame_reset_scroll: false,
             column_pinned: vec![Column::new("/#".to_string(), ValueType::Number)],
+            hidden_columns: HashSet::new(),
+            columns_order: vec![],
             scroll_y: 0.0,
             hovered_row_index: None,
             columns_offset: vec![],
@@ -296,6 +490,9 @@ impl ArrayTable {
             columns_filter: HashMap::new(),
             scroll_to_row_mode: ScrollToRowMode::RowNumber,
             scroll_to_row: "".to_string(),
+            search_options: SearchOptions::default(),
+            search_error: None,
+            search_regex: None,
             changed_scroll_to_row_value: None,
             changed_matching_row_selected: false,
             changed_matching_column_selected: false,
@@ -303,7 +500,36 @@ impl ArrayTable {
             editing_value: RefCell::new(String::new()),
             is_sub_table: false,
             focused_cell: None,
+            selection: None,
+            scroll_x: 0.0,
+            scroll_view_width: 0.0,
+            scroll_content_width: 0.0,
+            visible_rows_count: 0,
+            scroll_to_selection_cursor: false,
             cache: Default::default(),
+            find_replace_window: None,
+            column_chooser_window: None,
+            column_profile_windows: vec![],
+            unnest_windows: vec![],
+            undo_step: vec![],
+            undo_stack: vec![],
+            schema_validator: None,
+            violations: BTreeMap::new(),
+            show_violations: false,
+            invalid_rows_only: false,
+            conversion_report: None,
+            column_widths: HashMap::new(),
+            column_widths_changed: false,
+            auto_fit_columns: vec![],
+            collapsed_groups: HashSet::new(),
+            show_record_view: false,
+            record_view: None,
+            show_raw_json: false,
+            raw_json_view: None,
+            show_tree: false,
+            tree_view: None,
+            changed_rows: BTreeSet::new(),
+            unnest_path: None,
         }
     }
     pub fn windows(&mut self, ctx: &Context, array_response: &mut ArrayResponse) {
@@ -328,43 +554,913 @@ impl ArrayTable {
                 array_response.edited_value = Some(updated_value.0.clone())
             }
         }
-        self.windows.retain(|w| !closed_windows.contains(w.name()));
+        self.windows.retain(|w| !closed_windows.contains(w.name()));
+    }
+
+    pub fn open_find_replace(&mut self) {
+        if self.find_replace_window.is_none() {
+            let column = self.focused_cell.and_then(|(col_index, _, pinned)| {
+                let columns = if pinned { &self.column_pinned } else { &self.column_selected };
+                columns.get(col_index).map(|c| c.name.clone())
+            });
+            self.find_replace_window = Some(FindReplaceWindow::new(column));
+        }
+    }
+
+    fn find_replace_window(&mut self, ctx: &Context, array_response: &mut ArrayResponse) {
+        let Some(mut window) = self.find_replace_window.take() else {
+            return;
+        };
+        let columns = self.column_pinned.iter().chain(self.column_selected.iter())
+            .filter(|c| Self::is_filterable(c) && !c.name.is_empty() && !c.name.eq("/#"))
+            .collect::<Vec<&Column>>();
+        let mut opened = true;
+        let action = window.show(ctx, &mut opened, &columns);
+        if !matches!(action, FindReplaceAction::None) {
+            // Preview may be outdated when rows changed since it was computed, so matches are computed against current rows
+            let all_rows = (0..self.nodes.len()).collect::<Vec<usize>>();
+            let rows = if window.filtered_rows_only { &self.filtered_nodes } else { &all_rows };
+            window.compute_replacements(&self.nodes, rows, &self.parent_pointer, &columns);
+        }
+        if matches!(action, FindReplaceAction::Apply) {
+            for replacement in mem::take(&mut window.replacements) {
+                let mut pointer = replacement.pointer;
+                // A number or boolean which is no more valid after replacement is written as a string
+                if matches!(pointer.value_type, ValueType::Number) && replacement.new_value.parse::<f64>().is_err()
+                    || matches!(pointer.value_type, ValueType::Bool) && !matches!(replacement.new_value.as_str(), "true" | "false") {
+                    pointer.value_type = ValueType::String;
+                }
+                let entry = FlatJsonValue { pointer, value: Some(replacement.new_value) };
+                if self.update_value(entry.clone(), replacement.row_index, true) {
+                    array_response.edited_value = Some(entry);
+                }
+            }
+            self.refresh_search();
+        }
+        if opened {
+            self.find_replace_window = Some(window);
+        }
+    }
+
+    fn columns_count(&self) -> usize {
+        self.column_pinned.len() + self.column_selected.len()
+    }
+
+    /// Column at index going through pinned columns then selected columns.
+    fn column_at(&self, index: usize) -> Option<&Column> {
+        self.column_pinned.get(index).or_else(|| self.column_selected.get(index.wrapping_sub(self.column_pinned.len())))
+    }
+
+    fn select_cell(&mut self, column: usize, row: usize, extend: bool) {
+        match self.selection.as_mut() {
+            Some(selection) if extend => selection.cursor = (column, row),
+            _ => self.selection = Some(CellSelection::new((column, row)))
+        }
+    }
+
+    fn select_row(&mut self, row: usize, extend: bool) {
+        let last_column = self.columns_count() - 1;
+        let anchor_row = self.selection.filter(|_| extend).map_or(row, |s| s.anchor.1);
+        self.selection = Some(CellSelection { anchor: (0, anchor_row), cursor: (last_column, row) });
+    }
+
+    fn select_column(&mut self, column: usize, extend: bool) {
+        let last_row = self.filtered_nodes.len().saturating_sub(1);
+        let anchor_column = self.selection.filter(|_| extend).map_or(column, |s| s.anchor.0);
+        self.selection = Some(CellSelection { anchor: (anchor_column, 0), cursor: (column, last_row) });
+    }
+
+    /// Selected columns and rows ranges, with columns index relative to pinned or non pinned table.
+    fn selected_cells(&self, pinned_column_table: bool) -> Option<(RangeInclusive<usize>, RangeInclusive<usize>)> {
+        let selection = self.selection?;
+        let (start, end) = selection.columns().into_inner();
+        let pinned_count = self.column_pinned.len();
+        let columns = if pinned_column_table {
+            if start >= pinned_count {
+                return None;
+            }
+            start..=end.min(pinned_count - 1)
+        } else {
+            if end < pinned_count {
+                return None;
+            }
+            start.saturating_sub(pinned_count)..=end - pinned_count
+        };
+        Some((columns, selection.rows()))
+    }
+
+    /// Move selection cursor with arrows, page up/down, home/end (shift extends selection), Enter or F2 edit cursor cell,
+    /// copy and paste selected cells as tab separated values.
+    fn handle_keyboard_input(&mut self, ui: &Ui) -> Option<FlatJsonValue<String>> {
+        if self.filtered_nodes.is_empty() {
+            return None;
+        }
+        let last_column = self.columns_count() - 1;
+        let last_row = self.filtered_nodes.len() - 1;
+        let page = self.visible_rows_count.max(1);
+        let cursor = self.selection.map_or((1.min(last_column), 0), |selection| selection.cursor);
+        let (moved_cursor, extend, edit) = ui.input_mut(|i| {
+            // Shift is ignored when matching keys, it is only used to extend selection
+            let moved_cursor = if i.consume_key(Modifiers::COMMAND, Key::Home) {
+                Some((0, 0))
+            } else if i.consume_key(Modifiers::COMMAND, Key::End) {
+                Some((last_column, last_row))
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowUp) {
+                Some((cursor.0, cursor.1.saturating_sub(1)))
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowDown) {
+                Some((cursor.0, (cursor.1 + 1).min(last_row)))
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowLeft) {
+                Some((cursor.0.saturating_sub(1), cursor.1))
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowRight) {
+                Some(((cursor.0 + 1).min(last_column), cursor.1))
+            } else if i.consume_key(Modifiers::NONE, Key::PageUp) {
+                Some((cursor.0, cursor.1.saturating_sub(page)))
+            } else if i.consume_key(Modifiers::NONE, Key::PageDown) {
+                Some((cursor.0, (cursor.1 + page).min(last_row)))
+            } else if i.consume_key(Modifiers::NONE, Key::Home) {
+                Some((0, cursor.1))
+            } else if i.consume_key(Modifiers::NONE, Key::End) {
+                Some((last_column, cursor.1))
+            } else {
+                None
+            };
+            let edit = moved_cursor.is_none() && (i.consume_key(Modifiers::NONE, Key::Enter) || i.consume_key(Modifiers::NONE, Key::F2));
+            (moved_cursor, i.modifiers.shift, edit)
+        });
+        if let Some(moved_cursor) = moved_cursor {
+            self.select_cell(moved_cursor.0, moved_cursor.1, extend);
+            self.scroll_to_selection_cursor = true;
+        }
+        if edit {
+            if let Some(selection) = self.selection {
+                self.edit_cell(selection.cursor);
+            }
+        }
+        let mut pasted_text = None;
+        let mut copy = false;
+        ui.input(|i| {
+            for event in i.events.iter() {
+                match event {
+                    Event::Copy => copy = true,
+                    Event::Paste(text) => pasted_text = Some(text.clone()),
+                    _ => {}
+                }
+            }
+        });
+        if copy && self.selection.is_some() {
+            ui.ctx().copy_text(self.selection_to_tsv());
+        }
+        let text = pasted_text?;
+        match crate::clipboard::parse_json_rows(&text) {
+            Some(rows) => self.append_rows(rows),
+            None => self.paste_tsv(&text)
+        }
+    }
+
+    /// Start editing cell at (column, row) selection coordinates, row number column is not editable.
+    fn edit_cell(&self, (column, row): (usize, usize)) {
+        let Some(row_index) = self.filtered_nodes.get(row).copied() else {
+            return;
+        };
+        let Some(column_data) = self.column_at(column).filter(|c| !c.name.is_empty() && !c.name.eq("/#")) else {
+            return;
+        };
+        let row_data = &self.nodes[row_index];
+        let value = match Self::get_pointer_for_column(&self.parent_pointer, &&row_data.entries, row_data.index, column_data) {
+            Some(entry) => entry.value.clone().filter(|_| !matches!(entry.pointer.value_type, ValueType::Null)).unwrap_or_default(),
+            None => self.default_value(column_data),
+        };
+        let pinned_count = self.column_pinned.len();
+        let (col_index, pinned_column_table) = if column < pinned_count { (column, true) } else { (column - pinned_count, false) };
+        *self.editing_value.borrow_mut() = value;
+        *self.editing_index.borrow_mut() = Some((col_index, row_index, pinned_column_table));
+    }
+
+    /// Value pre-filled when editing a key missing from row: schema default if any.
+    fn default_value(&self, column: &Column) -> String {
+        self.schema_validator.as_ref()
+            .and_then(|validator| validator.property_schema(&column.name))
+            .and_then(crate::schema::default_value)
+            .unwrap_or_default()
+    }
+
+    /// Next (or previous) editable cell, going to next (or previous) row after last (or first) column.
+    fn next_editable_cell(&self, (column, row): (usize, usize), backward: bool) -> Option<(usize, usize)> {
+        let columns_count = self.columns_count();
+        let mut position = row * columns_count + column;
+        loop {
+            position = if backward { position.checked_sub(1)? } else { position + 1 };
+            let (column, row) = (position % columns_count, position / columns_count);
+            if row >= self.filtered_nodes.len() {
+                return None;
+            }
+            if self.column_at(column).is_some_and(|c| !c.name.is_empty() && !c.name.eq("/#")) {
+                return Some((column, row));
+            }
+        }
+    }
+
+    /// Horizontal offset to apply to non pinned table so selection cursor column is visible, None when already visible.
+    fn scroll_x_to_selection_cursor(&self) -> Option<f32> {
+        let column = self.selection?.cursor.0.checked_sub(self.column_pinned.len())?;
+        let start = *self.columns_offset.get(column)?;
+        let end = self.columns_offset.get(column + 1).copied().unwrap_or(self.scroll_content_width);
+        if start < self.scroll_x {
+            Some(start)
+        } else if end > self.scroll_x + self.scroll_view_width {
+            Some((end - self.scroll_view_width).min(start))
+        } else {
+            None
+        }
+    }
+
+    /// Rows (as position in filtered rows) and columns targeted by a context menu opened on a cell:
+    /// the selection when it contains the cell, otherwise the cell row with all columns.
+    fn context_menu_range(&self, column: usize, table_row_index: usize) -> (RangeInclusive<usize>, Vec<&Column>) {
+        let selection = self.selection.filter(|s| s.columns().contains(&column) && s.rows().contains(&table_row_index));
+        let (rows, columns) = match selection {
+            Some(selection) => (selection.rows(), selection.columns()),
+            None => (table_row_index..=table_row_index, 0..=self.columns_count() - 1),
+        };
+        let columns = columns.filter_map(|index| self.column_at(index))
+            .filter(|column| !column.name.is_empty() && !column.name.eq("/#"))
+            .collect::<Vec<&Column>>();
+        (rows, columns)
+    }
+
+    /// Serialized root node of the row, kept as last entry.
+    fn row_json(&self, row_index: usize) -> &str {
+        self.nodes[row_index].entries.last().and_then(|entry| entry.value.as_deref()).unwrap_or("null")
+    }
+
+    fn cell_value(&self, row_index: usize, column: &Column) -> String {
+        let row_data = &self.nodes[row_index];
+        Self::get_pointer_for_column(&self.parent_pointer, &&row_data.entries, row_data.index, column)
+            .filter(|entry| !matches!(entry.pointer.value_type, ValueType::Null))
+            .and_then(|entry| entry.value.as_ref().map(|value| value.replace('\n', "")))
+            .unwrap_or_default()
+    }
+
+    fn copy_rows_menu(&self, ui: &mut Ui, column: usize, table_row_index: usize) {
+        let (rows, columns) = self.context_menu_range(column, table_row_index);
+        if ui.button("Copy row as JSON").clicked() {
+            ui.ctx().copy_text(self.row_json(self.filtered_nodes[table_row_index]).to_string());
+            ui.close_menu();
+        }
+        if ui.button("Copy selected rows as JSON array").clicked() {
+            let json_rows = rows.clone().filter_map(|row| self.filtered_nodes.get(row)).map(|row_index| self.row_json(*row_index)).collect::<Vec<&str>>();
+            ui.ctx().copy_text(concat_string!("[", json_rows.join(","), "]"));
+            ui.close_menu();
+        }
+        if ui.add_enabled(self.column_at(column).is_some_and(|c| !c.name.eq("/#")), Button::new("Copy column values")).clicked() {
+            let column = self.column_at(column).unwrap();
+            let values = self.filtered_nodes.iter().map(|row_index| self.cell_value(*row_index, column)).collect::<Vec<String>>();
+            ui.ctx().copy_text(values.join("\n"));
+            ui.close_menu();
+        }
+        if ui.button("Copy as Markdown table").clicked() {
+            let header = columns.iter().map(|column| column.name.as_str()).collect::<Vec<&str>>();
+            let values = rows.filter_map(|row| self.filtered_nodes.get(row))
+                .map(|row_index| columns.iter().map(|column| self.cell_value(*row_index, column)).collect::<Vec<String>>())
+                .collect::<Vec<Vec<String>>>();
+            ui.ctx().copy_text(crate::clipboard::to_markdown_table(&header, &values));
+            ui.close_menu();
+        }
+    }
+
+    /// Append json objects as new rows, then select first added row. Appended rows are removed by undo.
+    fn append_rows(&mut self, rows: Vec<String>) -> Option<FlatJsonValue<String>> {
+        let (depth, value_type) = self.nodes.last().and_then(|row| row.entries.last())
+            .map_or((1, ValueType::Object(true)), |root| (root.pointer.depth, root.pointer.value_type));
+        let first_added_row = self.nodes.len();
+        let mut edited_value = None;
+        for json in rows {
+            let index = self.nodes.len();
+            let prefix = concat_string!(self.parent_pointer, "/", index.to_string());
+            let result = match JSONParser::parse(json.as_str(), ParseOptions::default().prefix(prefix.clone())
+                .start_depth(depth + 1).parse_array(false).max_depth(self.last_parsed_max_depth)) {
+                Ok(result) => result.to_owned(),
+                Err(e) => {
+                    log!("Failed to parse pasted row: {}", e);
+                    continue;
+                }
+            };
+            let mut entries = Vec::with_capacity(result.json.len() + 2);
+            entries.push(FlatJsonValue { pointer: PointerKey::from_pointer_and_index(concat_string!(prefix, "/#"), ValueType::Number, 0, index, 0), value: Some(index.to_string()) });
+            for mut entry in result.json {
+                entry.pointer.index = index;
+                let name = &entry.pointer.pointer[prefix.len()..];
+                if !name.is_empty() {
+                    if let Some(column) = self.all_columns.iter_mut().find(|c| c.name.eq(name)) {
+                        column.seen_count += 1;
+                        column.add_observed_type(entry.pointer.value_type);
+                        if matches!(column.value_type, ValueType::Null) {
+                            column.value_type = entry.pointer.value_type;
+                        }
+                    } else {
+                        self.all_columns.push(Column {
+                            name: name.to_string(),
+                            depth: entry.pointer.depth,
+                            value_type: entry.pointer.value_type,
+                            seen_count: 1,
+                            order: self.all_columns.len(),
+                            observed_types: vec![entry.pointer.value_type],
+                        });
+                    }
+                }
+                entries.push(entry);
+            }
+            let root = FlatJsonValue { pointer: PointerKey { pointer: prefix, value_type, depth, index, position: 0 }, value: Some(json) };
+            entries.push(root.clone());
+            Arc::make_mut(&mut self.nodes).push(JsonArrayEntries { entries, index });
+            edited_value = Some(root);
+        }
+        if edited_value.is_some() {
+            if !self.is_sub_table {
+                self.undo_step.push(UndoEntry::AppendedRows(first_added_row));
+            }
+            self.all_columns.sort();
+            self.update_selected_columns(self.max_depth);
+            for row_index in first_added_row..self.nodes.len() {
+                self.validate_row(row_index);
+            }
+            self.refresh_filtered_nodes();
+            if let Some(table_row_index) = self.filtered_nodes.iter().position(|row_index| *row_index >= first_added_row) {
+                self.select_row(table_row_index, false);
+                self.scroll_to_selection_cursor = true;
+            }
+            self.refresh_search();
+        }
+        edited_value
+    }
+
+    fn selection_to_tsv(&self) -> String {
+        let Some(selection) = self.selection else {
+            return String::new();
+        };
+        let columns = selection.columns().filter_map(|index| self.column_at(index))
+            .filter(|column| !column.name.is_empty() && !column.name.eq("/#"))
+            .collect::<Vec<&Column>>();
+        let mut rows = vec![];
+        for table_row_index in selection.rows() {
+            let Some(row_index) = self.filtered_nodes.get(table_row_index) else {
+                break;
+            };
+            rows.push(columns.iter().map(|column| self.cell_value(*row_index, column)).collect::<Vec<String>>());
+        }
+        crate::clipboard::to_tsv(&rows)
+    }
+
+    /// Write tab separated values starting at selection top left cell. A single value fills the whole selection.
+    fn paste_tsv(&mut self, text: &str) -> Option<FlatJsonValue<String>> {
+        let selection = self.selection?;
+        let mut values = crate::clipboard::parse_tsv(text);
+        if values.is_empty() {
+            return None;
+        }
+        if values.len() == 1 && values[0].len() == 1 {
+            let value = values[0][0].clone();
+            values = selection.rows().map(|_| selection.columns().map(|_| value.clone()).collect()).collect();
+        }
+        let start_column = *selection.columns().start();
+        let start_row = *selection.rows().start();
+        let mut edited_value = None;
+        for (i, row_values) in values.into_iter().enumerate() {
+            let Some(row_index) = self.filtered_nodes.get(start_row + i).copied() else {
+                break;
+            };
+            for (j, value) in row_values.into_iter().enumerate() {
+                let Some(column) = self.column_at(start_column + j) else {
+                    break;
+                };
+                if column.name.is_empty() || column.name.eq("/#") || matches!(column.value_type, ValueType::Object(_) | ValueType::Array(_)) {
+                    continue;
+                }
+                let pointer = PointerKey {
+                    pointer: Self::pointer_key(&self.parent_pointer, self.nodes[row_index].index, &column.name),
+                    value_type: infer_value_type(&value),
+                    depth: column.depth,
+                    index: row_index,
+                    position: 0,
+                };
+                let entry = FlatJsonValue { pointer, value: if value.is_empty() { None } else { Some(value) } };
+                if self.update_value(entry.clone(), row_index, true) {
+                    edited_value = Some(entry);
+                }
+            }
+        }
+        if edited_value.is_some() {
+            self.refresh_search();
+        }
+        edited_value
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Restore values changed by last edit, returns last restored entry when something changed.
+    pub fn undo(&mut self) -> Option<FlatJsonValue<String>> {
+        self.end_undo_step();
+        let step = self.undo_stack.pop()?;
+        let mut restored_entry = None;
+        for undo_entry in step.into_iter().rev() {
+            match undo_entry {
+                UndoEntry::Value(row_index, entry) => {
+                    if self.update_value(entry.clone(), row_index, true) {
+                        restored_entry = Some(entry);
+                    }
+                }
+                UndoEntry::AppendedRows(first_row_index) => {
+                    if let Some(removed_root) = self.remove_rows_from(first_row_index) {
+                        restored_entry = Some(removed_root);
+                    }
+                }
+            }
+        }
+        // Restoring values should not be undoable itself
+        self.undo_step.clear();
+        self.refresh_search();
+        restored_entry
+    }
+
+    /// Remove rows starting at `first_row_index` along with columns only seen in them, returns root of last removed row.
+    fn remove_rows_from(&mut self, first_row_index: usize) -> Option<FlatJsonValue<String>> {
+        if first_row_index >= self.nodes.len() {
+            return None;
+        }
+        let removed_rows = Arc::make_mut(&mut self.nodes).split_off(first_row_index);
+        for row in removed_rows.iter() {
+            let prefix_len = self.parent_pointer.len() + 1 + row.index.to_string().len();
+            // First entry is row number and last one is row root, neither is counted in columns
+            for entry in row.entries.iter().skip(1).take(row.entries.len().saturating_sub(2)) {
+                if let Some(column) = self.all_columns.iter_mut().find(|c| c.name.eq(&entry.pointer.pointer[prefix_len..])) {
+                    column.seen_count = column.seen_count.saturating_sub(1);
+                    if column.seen_count == 0 {
+                        let name = column.name.clone();
+                        self.all_columns.retain(|c| !c.name.eq(&name));
+                    }
+                }
+            }
+        }
+        self.violations.split_off(&first_row_index);
+        self.windows.retain(|subtable| subtable.id() < first_row_index);
+        if let Some(tree_view) = self.tree_view.as_mut() {
+            tree_view.invalidate();
+        }
+        self.all_columns.sort();
+        self.update_selected_columns(self.max_depth);
+        self.refresh_filtered_nodes();
+        removed_rows.into_iter().last().and_then(|row| row.entries.into_iter().last())
+    }
+
+    fn end_undo_step(&mut self) {
+        if !self.undo_step.is_empty() {
+            if self.undo_stack.len() == UNDO_HISTORY_SIZE {
+                self.undo_stack.remove(0);
+            }
+            self.undo_stack.push(mem::take(&mut self.undo_step));
+        }
+    }
+
+    pub fn update_selected_columns(&mut self, depth: u8) -> Option<usize> {
+        self.selection = None;
+        if depth <= self.last_parsed_max_depth {
+            self.refresh_selected_columns(depth);
+            None
+        } else {
+            let previous_parse_result = self.parse_result.clone().unwrap();
+            let (new_json_array, new_columns, new_max_depth) = crate::parser::change_depth_array(previous_parse_result, Arc::unwrap_or_clone(mem::take(&mut self.nodes)), depth as usize).unwrap();
+            self.all_columns = new_columns;
+            self.refresh_selected_columns(depth);
+            self.nodes = Arc::new(new_json_array);
+            self.last_parsed_max_depth = depth;
+            self.parse_result.as_mut().unwrap().parsing_max_depth = depth;
+            self.parse_result.as_mut().unwrap().max_json_depth = new_max_depth;
+            Some(new_max_depth)
+        }
+    }
+    /// Columns visible at depth, without pinned and hidden columns, in user defined order.
+    fn refresh_selected_columns(&mut self, depth: u8) {
+        let mut column_selected = Self::selected_columns(&self.all_columns, depth);
+        column_selected.retain(|c| !self.column_pinned.contains(c) && !self.hidden_columns.contains(&c.name));
+        self.sort_by_columns_order(&mut column_selected);
+        for group in self.collapsed_groups.iter() {
+            let prefix = concat_string!(group, "/");
+            if let Some(position) = column_selected.iter().position(|c| c.name.starts_with(&prefix)) {
+                column_selected.retain(|c| !c.name.starts_with(&prefix) && !c.name.eq(group));
+                let column = self.all_columns.iter().find(|c| c.name.eq(group)).cloned()
+                    .unwrap_or_else(|| Column::new(group.clone(), ValueType::Object(false)));
+                column_selected.insert(position.min(column_selected.len()), column);
+            }
+        }
+        self.column_selected = column_selected;
+        if self.column_selected.is_empty() {
+            self.column_selected.push(Column {
+                name: "".to_string(),
+                depth,
+                value_type: Default::default(),
+                seen_count: 0,
+                order: 0,
+                observed_types: vec![],
+            })
+        }
+    }
+
+    fn sort_by_columns_order(&self, columns: &mut [Column]) {
+        let positions = self.columns_order.iter().enumerate().map(|(i, name)| (name.as_str(), i)).collect::<HashMap<&str, usize>>();
+        columns.sort_by(|a, b| {
+            let position_a = positions.get(a.name.as_str()).copied().unwrap_or(usize::MAX);
+            let position_b = positions.get(b.name.as_str()).copied().unwrap_or(usize::MAX);
+            position_a.cmp(&position_b).then_with(|| a.cmp(b))
+        });
+    }
+
+    pub fn open_column_chooser(&mut self) {
+        if self.column_chooser_window.is_none() {
+            self.column_chooser_window = Some(ColumnChooserWindow::default());
+        }
+    }
+
+    /// Non pinned columns visible at current depth in display order, hidden ones included.
+    fn chooser_columns(&self) -> Vec<Column> {
+        let mut columns = Self::visible_columns(&self.all_columns, self.max_depth)
+            .filter(|c| !self.column_pinned.contains(c))
+            .cloned()
+            .collect::<Vec<Column>>();
+        self.sort_by_columns_order(&mut columns);
+        columns
+    }
+
+    fn column_chooser_window(&mut self, ctx: &Context) {
+        let Some(mut window) = self.column_chooser_window.take() else {
+            return;
+        };
+        let columns = self.chooser_columns();
+        let columns_visibility = columns.iter().map(|c| (c, !self.hidden_columns.contains(&c.name))).collect::<Vec<(&Column, bool)>>();
+        let mut opened = true;
+        let action = window.show(ctx, &mut opened, &columns_visibility);
+        let changed = !matches!(action, ColumnChooserAction::None);
+        match action {
+            ColumnChooserAction::None => {}
+            ColumnChooserAction::Toggle(name) => {
+                if !self.hidden_columns.remove(&name) {
+                    self.hidden_columns.insert(name);
+                }
+            }
+            ColumnChooserAction::ShowAll => self.hidden_columns.clear(),
+            ColumnChooserAction::HideAll => self.hidden_columns.extend(columns.iter().map(|c| c.name.clone())),
+            ColumnChooserAction::Move(column, before) => self.move_column(&column, &before),
+            ColumnChooserAction::Reset => {
+                self.hidden_columns.clear();
+                self.columns_order.clear();
+            }
+        }
+        if changed {
+            self.update_selected_columns(self.max_depth);
+        }
+        if opened {
+            self.column_chooser_window = Some(window);
+        }
+    }
+
+    /// Open profile of column values, rows are shared so values can be extracted and profiled in background.
+    fn open_column_profile(&mut self, column: &Column) {
+        self.column_profile_windows.retain(|window| !window.column().eq(&column.name));
+        let values = ColumnProfile::column_values(&self.nodes, &self.parent_pointer, column);
+        self.column_profile_windows.push(ColumnProfileWindow::new(column.name.clone(), values));
+    }
+
+    /// Open a view having one row per element of array `column`, for filtered rows.
+    fn open_unnest(&mut self, column: String) {
+        self.unnest_windows.retain(|window| !window.column().eq(&column));
+        // Elements of array have one more level than the array itself
+        let depth = self.max_depth.max(column.matches('/').count() as u8 + 1);
+        let path = match self.unnest_path.as_ref() {
+            Some(path) => concat_string!(path, " > ", column),
+            None => column.clone(),
+        };
+        match UnnestWindow::new(column, path, self.unnest_rows(), depth) {
+            Ok(window) => self.unnest_windows.push(window),
+            Err(e) => log!("Failed to unnest column: {}", e),
+        }
+    }
+
+    fn unnest_rows(&self) -> Vec<(usize, &str)> {
+        self.filtered_nodes.iter().map(|row_index| (*row_index, self.row_json(*row_index))).collect()
+    }
+
+    fn unnest_windows(&mut self, ctx: &Context, array_response: &mut ArrayResponse) {
+        let mut edits = vec![];
+        self.unnest_windows.retain_mut(|window| {
+            let mut opened = true;
+            edits.extend(window.show(ctx, &mut opened));
+            opened
+        });
+        if let Some(edited_value) = self.apply_unnested_edits(edits) {
+            array_response.edited_value = Some(edited_value);
+        }
+    }
+
+    /// Unnested views reflect source rows, update them after rows changed.
+    fn refresh_unnest_windows(&mut self, changed_rows: &BTreeSet<usize>) {
+        if self.unnest_windows.is_empty() {
+            return;
+        }
+        let mut unnest_windows = mem::take(&mut self.unnest_windows);
+        let changed_rows = changed_rows.iter().filter(|row_index| **row_index < self.nodes.len())
+            .map(|row_index| (*row_index, self.row_json(*row_index))).collect::<Vec<(usize, &str)>>();
+        unnest_windows.retain_mut(|window| match window.refresh_rows(changed_rows.clone(), self.unnest_rows()) {
+            Ok(_) => true,
+            Err(e) => {
+                log!("Closing unnested view: {}", e);
+                false
+            }
+        });
+        self.unnest_windows = unnest_windows;
+    }
+
+    /// Write values edited in an unnested view back to their source rows.
+    fn apply_unnested_edits(&mut self, edits: Vec<UnnestedEdit>) -> Option<FlatJsonValue<String>> {
+        let mut edits_by_row: BTreeMap<usize, Vec<UnnestedEdit>> = BTreeMap::new();
+        for edit in edits {
+            edits_by_row.entry(edit.row_index).or_default().push(edit);
+        }
+        let mut edited_value = None;
+        for (row_index, edits) in edits_by_row {
+            let Ok(mut row) = serde_json::from_str::<serde_json::Value>(self.row_json(row_index)) else {
+                continue;
+            };
+            for edit in edits {
+                match row.pointer_mut(&edit.pointer) {
+                    Some(value) => *value = json_value(edit.value_type, edit.value.as_deref()),
+                    None => log!("{} not found in row {}, unnested value not written", edit.pointer, row_index),
+                }
+            }
+            match self.replace_row(row_index, row.to_string()) {
+                Ok(true) => edited_value = self.nodes[row_index].entries.last().cloned(),
+                Ok(false) => {}
+                Err(e) => log!("Failed to update row {}: {}", row_index, e),
+            }
+        }
+        edited_value
+    }
+
+    /// Replace row content with `json`, parsed once. Previous row root entry is pushed as a single undo entry,
+    /// undoing it goes through update_value which replaces row again.
+    pub(crate) fn replace_row(&mut self, row_index: usize, json: String) -> Result<bool, String> {
+        let root = self.nodes[row_index].entries.last().cloned().ok_or_else(|| "Row is empty".to_string())?;
+        let options = ParseOptions::default()
+            .prefix(root.pointer.pointer.clone())
+            .start_depth(root.pointer.depth + 1).parse_array(false)
+            .max_depth(self.last_parsed_max_depth);
+        let mut new_entries = JSONParser::parse(json.as_str(), options.clone()).map_err(|e| e.to_string())?.to_owned().json;
+        let line_number_entry = self.nodes[row_index].entries[0].clone();
+        new_entries.insert(0, line_number_entry);
+        // Serialize parsed entries, as update_value does, so root and nested raw values are written the same way for every row
+        let serialized_json = serialize_to_json_with_option::<String>(&mut new_entries.clone(), root.pointer.depth + 1).to_json();
+        if !serialized_json.eq(&json) {
+            new_entries.truncate(1);
+            new_entries.extend(JSONParser::parse(serialized_json.as_str(), options).map_err(|e| e.to_string())?.to_owned().json);
+        }
+        new_entries.push(FlatJsonValue { pointer: root.pointer.clone(), value: Some(serialized_json) });
+        if root.value.eq(&new_entries.last().unwrap().value) {
+            return Ok(false);
+        }
+
+        let previous_entries = mem::replace(&mut Arc::make_mut(&mut self.nodes)[row_index].entries, new_entries);
+        let previous_values = previous_entries.iter().map(|entry| (entry.pointer.pointer.as_str(), entry)).collect::<HashMap<&str, &FlatJsonValue<String>>>();
+        let mut changed_entries = self.nodes[row_index].entries.iter()
+            .filter(|entry| previous_values.get(entry.pointer.pointer.as_str())
+                .is_none_or(|previous| !previous.value.eq(&entry.value) || previous.pointer.value_type != entry.pointer.value_type))
+            .cloned().collect::<Vec<FlatJsonValue<String>>>();
+        let new_pointers = self.nodes[row_index].entries.iter().map(|entry| entry.pointer.pointer.as_str()).collect::<HashSet<&str>>();
+        changed_entries.extend(previous_entries.iter().filter(|entry| !new_pointers.contains(entry.pointer.pointer.as_str()))
+            .map(|entry| FlatJsonValue { pointer: entry.pointer.clone(), value: None }));
+        let row_prefix_len = root.pointer.pointer.len();
+        for entry in changed_entries {
+            if entry.value.is_some() && entry.pointer.pointer.len() > row_prefix_len {
+                self.add_observed_type(&entry.pointer.pointer[row_prefix_len..], entry.pointer.value_type);
+            }
+            if let Some(subtable) = self.windows.iter_mut().find(|subtable| subtable.id() == row_index) {
+                subtable.update_nodes(entry.pointer, entry.value);
+            }
+        }
+        if !self.is_sub_table {
+            self.undo_step.push(UndoEntry::Value(row_index, root));
+        }
+        self.changed_rows.insert(row_index);
+        self.validate_row(row_index);
+        Ok(true)
+    }
+
+    /// Set value at `pointer`, relative to row, then replace row with its updated json.
+    fn set_row_value(&mut self, row_index: usize, pointer: &str, value: serde_json::Value) -> Result<bool, String> {
+        let mut row = serde_json::from_str::<serde_json::Value>(self.row_json(row_index)).map_err(|e| e.to_string())?;
+        *row.pointer_mut(pointer).ok_or_else(|| format!("{} not found in row", pointer))? = value;
+        self.replace_row(row_index, row.to_string())
+    }
+
+    /// Write value edited as text in a side view, null values get the type of their new text.
+    /// Returns row root entry when row changed.
+    fn write_row_value(&mut self, row_index: usize, pointer: &str, value_type: ValueType, value: Option<&str>) -> Option<FlatJsonValue<String>> {
+        let value_type = match (value_type, value) {
+            (ValueType::Null, Some(value)) => infer_value_type(value),
+            (value_type, _) => value_type,
+        };
+        match self.set_row_value(row_index, pointer, json_value(value_type, value)) {
+            Ok(true) => self.nodes[row_index].entries.last().cloned(),
+            Ok(false) => None,
+            Err(e) => {
+                log!("Failed to update row {}: {}", row_index, e);
+                None
+            }
+        }
+    }
+
+    /// Cell under selection cursor, when its row is displayed.
+    fn selection_cursor(&self) -> Option<(usize, usize)> {
+        self.selection.map(|selection| selection.cursor).filter(|(_, row)| *row < self.filtered_nodes.len())
+    }
+
+    /// Document as a tree, selecting a node focuses its cell in table.
+    fn tree_panel(&mut self, ui: &mut Ui, array_response: &mut ArrayResponse) {
+        ui.horizontal(|ui| {
+            ui.strong("Tree");
+            ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Close").clicked() {
+                    self.show_tree = false;
+                }
+            });
+        });
+        ui.separator();
+        let tree_view = self.tree_view.get_or_insert_with(|| TreeView::new(self.parent_pointer.clone()));
+        match tree_view.ui(ui, &self.nodes) {
+            Some(TreeAction::Select { row_index, pointer }) => self.go_to_pointer(row_index, &pointer),
+            Some(TreeAction::Edit { row_index, pointer, value_type, value }) => {
+                if let Some(edited_value) = self.write_row_value(row_index, &pointer, value_type, value.as_deref()) {
+                    array_response.edited_value = Some(edited_value);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Pretty-printed json of focused row, applied text is parsed once and replaces row content as a single undo step.
+    fn raw_json_panel(&mut self, ui: &mut Ui, array_response: &mut ArrayResponse) {
+        ui.horizontal(|ui| {
+            ui.strong("JSON");
+            ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Close").clicked() {
+                    self.show_raw_json = false;
+                }
+            });
+        });
+        let Some((_, table_row_index)) = self.selection_cursor() else {
+            self.raw_json_view = None;
+            ui.label(egui::RichText::new("Select a cell to show its row").weak());
+            return;
+        };
+        let row_index = self.filtered_nodes[table_row_index];
+        ui.label(format!("#{}", self.nodes[row_index].index));
+        // Same as row_json, borrowing nodes only
+        let json = self.nodes[row_index].entries.last().and_then(|entry| entry.value.as_deref()).unwrap_or("null");
+        match self.raw_json_view.as_mut() {
+            // Text being edited is kept until row changes
+            Some(view) if view.is_editing() && view.row_index == row_index => {}
+            Some(view) if view.is_outdated(row_index, json) => view.rebuild(row_index, json.to_string()),
+            Some(_) => {}
+            None => self.raw_json_view = Some(RawJsonView::new(row_index, json.to_string())),
+        }
+        let Some(text) = self.raw_json_view.as_mut().and_then(|view| view.ui(ui)) else {
+            return;
+        };
+        match self.replace_row(row_index, text) {
+            Ok(changed) => {
+                if changed {
+                    array_response.edited_value = self.nodes[row_index].entries.last().cloned();
+                }
+                if let Some(view) = self.raw_json_view.as_mut() {
+                    view.stop_editing();
+                }
+            }
+            Err(e) => {
+                if let Some(view) = self.raw_json_view.as_mut() {
+                    view.set_error(e);
+                }
+            }
+        }
+    }
+
+    /// Focused row as a key/value list, previous and next buttons follow filtered and sorted rows.
+    fn record_view_panel(&mut self, ui: &mut Ui, array_response: &mut ArrayResponse) {
+        let cursor = self.selection_cursor();
+        let mut moved_to = None;
+        ui.horizontal(|ui| {
+            ui.strong("Record");
+            ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Close").clicked() {
+                    self.show_record_view = false;
+                }
+                if ui.add_enabled(cursor.is_some_and(|(_, row)| row + 1 < self.filtered_nodes.len()), Button::new("⏷").small())
+                    .on_hover_text("Next row").clicked() {
+                    moved_to = cursor.map(|(column, row)| (column, row + 1));
+                }
+                if ui.add_enabled(cursor.is_some_and(|(_, row)| row > 0), Button::new("⏶").small())
+                    .on_hover_text("Previous row").clicked() {
+                    moved_to = cursor.map(|(column, row)| (column, row - 1));
+                }
+            });
+        });
+        if let Some(cell) = moved_to {
+            self.selection = Some(CellSelection::new(cell));
+            self.scroll_to_selection_cursor = true;
+        }
+        let Some((_, table_row_index)) = moved_to.or(cursor) else {
+            self.record_view = None;
+            ui.label(egui::RichText::new("Select a cell to show its row").weak());
+            return;
+        };
+        let row_index = self.filtered_nodes[table_row_index];
+        ui.label(format!("#{} ({}/{})", self.nodes[row_index].index, table_row_index + 1, self.filtered_nodes.len()));
+        ui.separator();
+        if self.record_view.as_ref().is_none_or(|view| view.is_outdated(row_index, self.row_json(row_index))) {
+            let Some(root) = self.nodes[row_index].entries.last() else {
+                return;
+            };
+            let json = self.row_json(row_index).to_string();
+            match self.record_view.as_mut() {
+                Some(view) => view.rebuild(row_index, &root.pointer, json),
+                None => self.record_view = Some(RecordView::new(row_index, &root.pointer, json)),
+            }
+        }
+        let Some(edited_value) = self.record_view.as_mut().and_then(|view| view.ui(ui)) else {
+            return;
+        };
+        if let Some(edited_value) = self.write_row_value(row_index, &edited_value.pointer.pointer, edited_value.pointer.value_type, edited_value.value.as_deref()) {
+            array_response.edited_value = Some(edited_value);
+        }
     }
 
-    pub fn update_selected_columns(&mut self, depth: u8) -> Option<usize> {
-        if depth <= self.last_parsed_max_depth {
-            let mut column_selected = Self::selected_columns(&self.all_columns, depth);
-            column_selected.retain(|c| !self.column_pinned.contains(c));
-            self.column_selected = column_selected;
-            if self.column_selected.is_empty() {
-                self.column_selected.push(Column {
-                    name: "".to_string(),
-                    depth,
-                    value_type: Default::default(),
-                    seen_count: 0,
-                    order: 0,
-                })
-            }
-            None
-        } else {
-            let previous_parse_result = self.parse_result.clone().unwrap();
-            let (new_json_array, new_columns, new_max_depth) = crate::parser::change_depth_array(previous_parse_result, mem::take(&mut self.nodes), depth as usize).unwrap();
-            self.all_columns = new_columns;
-            let mut column_selected = Self::selected_columns(&self.all_columns, depth);
-            column_selected.retain(|c| !self.column_pinned.contains(c));
-            self.column_selected = column_selected;
-            self.nodes = new_json_array;
-            self.last_parsed_max_depth = depth;
-            self.parse_result.as_mut().unwrap().parsing_max_depth = depth;
-            self.parse_result.as_mut().unwrap().max_json_depth = new_max_depth;
-            Some(new_max_depth)
+    /// Move `column` before `before` column, both being non pinned columns.
+    fn move_column(&mut self, column: &str, before: &str) {
+        if column == before {
+            return;
         }
+        let mut columns_order = self.chooser_columns().into_iter().map(|c| c.name).collect::<Vec<String>>();
+        // Keep order of columns which are not visible at current depth
+        columns_order.extend(self.columns_order.iter().filter(|name| !columns_order.contains(name)).cloned().collect::<Vec<String>>());
+        let Some(from) = columns_order.iter().position(|name| name == column) else {
+            return;
+        };
+        let moved = columns_order.remove(from);
+        let to = columns_order.iter().position(|name| name == before).unwrap_or(columns_order.len());
+        columns_order.insert(to, moved);
+        self.columns_order = columns_order;
     }
+
     pub fn update_max_depth(&mut self, depth: u8) -> Option<usize> {
         self.max_depth = depth;
         self.update_selected_columns(depth)
     }
 
+    pub fn view_state(&self) -> ArrayTableViewState {
+        ArrayTableViewState {
+            scroll_y: self.scroll_y,
+            columns_filter: self.columns_filter.clone(),
+            column_pinned: self.column_pinned.clone(),
+            hidden_columns: self.hidden_columns.clone(),
+            columns_order: self.columns_order.clone(),
+            collapsed_groups: self.collapsed_groups.clone(),
+            schema: self.schema_validator.as_ref().map(|validator| validator.schema().clone()),
+            depth: self.max_depth,
+        }
+    }
+
+    /// Apply pinned columns, filters and scroll position of a previous table, depth has to be restored before.
+    pub fn restore_view_state(&mut self, state: ArrayTableViewState) {
+        let mut column_pinned = vec![self.column_pinned[0].clone()];
+        for column in state.column_pinned.iter().skip(1) {
+            if let Some(column) = self.all_columns.iter().find(|c| c.eq(&column)) {
+                column_pinned.push(column.clone());
+            }
+        }
+        self.column_pinned = column_pinned;
+        self.hidden_columns = state.hidden_columns;
+        self.columns_order = state.columns_order;
+        self.collapsed_groups = state.collapsed_groups;
+        self.refresh_selected_columns(self.max_depth);
+        self.columns_filter = state.columns_filter;
+        self.columns_filter.retain(|name, _| self.all_columns.iter().any(|c| c.name.eq(name)));
+        if let Some(schema) = state.schema {
+            if let Err(e) = self.attach_schema(schema) {
+                log!("Failed to attach schema again: {}", e);
+            }
+        }
+        self.refresh_filtered_nodes();
+        self.scroll_y = state.scroll_y;
+    }
+
     fn selected_columns(all_columns: &Vec<Column>, depth: u8) -> Vec<Column> {
         let mut column_selected: Vec<Column> = vec![];
         for col in Self::visible_columns(all_columns, depth) {
@@ -382,6 +1478,18 @@ impl ArrayTable {
         &self.all_columns
     }
 
+    pub fn column_pinned(&self) -> &Vec<Column> {
+        &self.column_pinned
+    }
+
+    pub fn column_selected(&self) -> &Vec<Column> {
+        &self.column_selected
+    }
+
+    pub fn filtered_nodes(&self) -> &Vec<usize> {
+        &self.filtered_nodes
+    }
+
     pub fn visible_columns(all_columns: &Vec<Column>, depth: u8) -> impl Iterator<Item=&Column> {
         all_columns.iter().filter(move |column: &&Column| column.depth == depth || (column.depth < depth && !matches!(column.value_type, ValueType::Object(_))))
     }
@@ -392,6 +1500,16 @@ impl ArrayTable {
         self.draw_table(ui, text_height, 7.0, pinned)
     }
 
+    /// Elements of array displayed as chips, parsed once for values displayed in consecutive frames.
+    fn cached_inline_items(&self, value: &str) -> Option<Vec<serde_json::Value>> {
+        if value.len() > CHIPS_MAX_JSON_LEN {
+            return None;
+        }
+        let mut cache_ref_mut = self.cache.borrow_mut();
+        let cache = cache_ref_mut.cache::<crate::components::cache::FrameCache<Option<Vec<serde_json::Value>>, CacheInlineItems>>();
+        cache.get(value, ())
+    }
+
     pub fn row_height(style: &Arc<Style>, spacing: &Spacing) -> f32 {
         egui::TextStyle::Body
             .resolve(style)
@@ -410,6 +1528,7 @@ impl ArrayTable {
             .min_scrolled_height(0.0)
             .max_scroll_height(parent_height)
             .set_is_pinned_column_table(pinned_column_table)
+            .selected_cells(self.selected_cells(pinned_column_table))
             .scroll_bar_visibility(if pinned_column_table { ScrollBarVisibility::AlwaysHidden } else { ScrollBarVisibility::AlwaysVisible })
             ;
 
@@ -430,7 +1549,18 @@ impl ArrayTable {
                     if changed_scroll_to_row_value.elapsed().as_millis() >= 300 {
                         self.changed_scroll_to_row_value = None;
                         if !self.scroll_to_row.is_empty() {
-                            self.matching_rows = search_occurrences(&self.nodes, &self.scroll_to_row.to_lowercase());
+                            match search_regex(&self.scroll_to_row, &self.search_options) {
+                                Ok(regex) => {
+                                    self.matching_rows = search_occurrences(&self.nodes, &self.filtered_nodes, &self.parent_pointer, &regex, &self.search_options);
+                                    self.search_regex = Some(regex);
+                                    self.search_error = None;
+                                }
+                                Err(e) => {
+                                    self.matching_rows.clear();
+                                    self.search_regex = None;
+                                    self.search_error = Some(e);
+                                }
+                            }
                             self.matching_row_selected = 0;
                             if !self.matching_rows.is_empty() {
                                 self.changed_matching_row_selected = true;
@@ -440,6 +1570,11 @@ impl ArrayTable {
                 }
             }
         }
+        if self.scroll_to_selection_cursor {
+            if let Some(selection) = self.selection {
+                table = table.scroll_to_row(selection.cursor.1, None);
+            }
+        }
         if self.changed_matching_row_selected {
             self.changed_matching_row_selected = false;
             table = table.scroll_to_row(self.matching_rows[self.matching_row_selected], Some(Align::Center));
@@ -450,7 +1585,10 @@ impl ArrayTable {
         let columns = if pinned_column_table { &self.column_pinned } else { &self.column_selected };
         if columns_count <= 3 {
             for i in 0..columns_count {
-                if pinned_column_table && i == 0 {
+                let width = self.column_widths.get(&columns[i].name).copied();
+                if let Some(width) = width {
+                    table = table.column(Column::initial(width).width(Some(width)).clip(true).resizable(true));
+                } else if pinned_column_table && i == 0 {
                     table = table.column(Column::initial(40.0).clip(true).resizable(true));
                 } else {
                     table = table.column(Column::auto().clip(true).resizable(true));
@@ -458,12 +1596,13 @@ impl ArrayTable {
             }
         } else {
             for i in 0..columns_count {
+                let width = self.column_widths.get(&columns[i].name).copied();
                 if pinned_column_table && i == 0 {
-                    table = table.column(Column::initial(40.0).clip(true).resizable(true));
+                    table = table.column(Column::initial(40.0).width(width).clip(true).resizable(true));
                     continue;
                 }
                 // table = table.column(Column::initial(10.0).clip(true).resizable(true));
-                table = table.column(Column::initial((columns[i].name.len() + 3).max(10) as f32 * text_width).clip(true).resizable(true));
+                table = table.column(Column::initial((columns[i].name.len() + 3).max(10) as f32 * text_width).width(width).clip(true).resizable(true));
             }
         }
 
@@ -474,8 +1613,8 @@ impl ArrayTable {
             None
         };
         let table_scroll_output = table
-            .header(text_height * 2.0, |mut header| {
-                self.header(pinned_column_table, header);
+            .header(text_height * if self.has_column_groups() { 3.0 } else { 2.0 }, |header| {
+                self.header(pinned_column_table, &mut array_response, header);
             })
             .body(self.hovered_row_index, search_highlight_row, self.focused_cell, |body| {
                 self.body(text_height, pinned_column_table, &mut array_response, request_repaint, body);
@@ -484,8 +1623,24 @@ impl ArrayTable {
         if self.scroll_y != table_scroll_output.state.offset.y {
             self.scroll_y = table_scroll_output.state.offset.y;
         }
+        let table_output = table_scroll_output.inner;
+        let columns = if pinned_column_table { &self.column_pinned } else { &self.column_selected };
+        if let Some(index) = table_output.resizing_column.or(table_output.resized_column) {
+            if let (Some(column), Some(width)) = (columns.get(index), table_output.column_widths.get(index)) {
+                self.column_widths.insert(column.name.clone(), *width);
+                self.column_widths_changed |= table_output.resized_column.is_some();
+            }
+        }
+        if let Some(column) = table_output.auto_fit_column.and_then(|index| columns.get(index)) {
+            self.auto_fit_columns.push(column.name.clone());
+        }
+        if !self.auto_fit_columns.is_empty() {
+            self.auto_fit(ui, text_height, pinned_column_table);
+        }
         if !pinned_column_table {
-            self.columns_offset = table_scroll_output.inner;
+            self.visible_rows_count = (table_scroll_output.inner_rect.height() / (text_height + ui.spacing().item_spacing.y)) as usize;
+            self.columns_offset = table_output.columns_offset;
+            self.paint_matching_rows_markers(ui, table_scroll_output.inner_rect);
         }
         if request_repaint {
             ui.ctx().request_repaint();
@@ -493,19 +1648,248 @@ impl ArrayTable {
         array_response
     }
 
-    fn header(&mut self, pinned_column_table: bool, mut header: TableRow) {
+    /// Top level object a nested column belongs to, e.g. `/address` for `/address/geo/lat`, or the collapsed object column itself.
+    fn column_group<'a>(&self, name: &'a str) -> Option<&'a str> {
+        if self.collapsed_groups.contains(name) {
+            return Some(name);
+        }
+        name.get(1..)?.find('/').map(|position| &name[..position + 1])
+    }
+
+    fn has_column_groups(&self) -> bool {
+        self.column_pinned.iter().chain(self.column_selected.iter()).any(|column| self.column_group(&column.name).is_some())
+    }
+
+    /// For each column, first and last index of the contiguous columns sharing its group.
+    fn column_group_runs(&self, pinned_column_table: bool) -> Vec<Option<(usize, usize)>> {
+        let columns = if pinned_column_table { &self.column_pinned } else { &self.column_selected };
+        let groups = columns.iter().map(|column| self.column_group(&column.name)).collect::<Vec<Option<&str>>>();
+        let mut runs = vec![None; columns.len()];
+        let mut first = 0;
+        for i in 0..groups.len() {
+            if groups[i] != groups[first] {
+                first = i;
+            }
+            if groups[i].is_some() && (i + 1 == groups.len() || groups[i + 1] != groups[i]) {
+                runs[first..=i].fill(Some((first, i)));
+            }
+        }
+        runs
+    }
+
+    /// Draw the part of a group header within a column header cell, group name sticks to the left edge while group is scrolled horizontally.
+    fn paint_column_group(&self, ui: &Ui, group_rect: Rect, group: &str, pinned_column_table: bool, index: usize, (first, last): (usize, usize)) {
+        let painter = ui.painter();
+        let color = ui.visuals().text_color();
+        let bracket_y = group_rect.bottom() - 2.0;
+        let bracket_left = if index == first { group_rect.left() + 2.0 } else { group_rect.left() - ui.spacing().item_spacing.x };
+        let bracket_right = if index == last { group_rect.right() - 2.0 } else { group_rect.right() + ui.spacing().item_spacing.x };
+        painter.hline(bracket_left..=bracket_right, bracket_y, Stroke::new(1.0, ui.visuals().widgets.noninteractive.fg_stroke.color));
+
+        let galley = painter.layout_no_wrap(group[1..].to_string(), egui::TextStyle::Body.resolve(ui.style()), color);
+        let icon_size = ui.spacing().icon_width * 0.6;
+        let label_width = icon_size + ui.spacing().item_spacing.x + galley.size().x;
+        let label_left = match (pinned_column_table, self.columns_offset.get(first), self.columns_offset.get(index)) {
+            (false, Some(group_start), Some(cell_start)) => {
+                let group_end = self.columns_offset.get(last + 1).copied().unwrap_or(self.scroll_content_width);
+                let label_start = self.scroll_x.min(group_end - label_width).max(*group_start);
+                group_rect.left() - cell_start + label_start
+            }
+            _ if index == first => group_rect.left(),
+            _ => return
+        };
+        let center_y = group_rect.center().y - 1.0;
+        let collapsed = self.collapsed_groups.contains(group);
+        let points = if collapsed {
+            vec![egui::pos2(label_left, center_y - icon_size / 2.0), egui::pos2(label_left + icon_size, center_y), egui::pos2(label_left, center_y + icon_size / 2.0)]
+        } else {
+            vec![egui::pos2(label_left, center_y - icon_size / 2.0), egui::pos2(label_left + icon_size, center_y - icon_size / 2.0), egui::pos2(label_left + icon_size / 2.0, center_y + icon_size / 2.0)]
+        };
+        if !pinned_column_table {
+            painter.add(egui::Shape::convex_polygon(points, color, Stroke::NONE));
+        }
+        let text_left = if pinned_column_table { label_left } else { label_left + icon_size + ui.spacing().item_spacing.x };
+        painter.galley(egui::pos2(text_left, center_y - galley.size().y / 2.0), galley, color);
+    }
+
+    /// Collapse nested columns of a top level object into a single column, or expand them back.
+    fn toggle_column_group(&mut self, group: String) {
+        if !self.collapsed_groups.remove(&group) {
+            self.collapsed_groups.insert(group);
+        }
+        self.selection = None;
+        self.refresh_selected_columns(self.max_depth);
+    }
+
+    /// Fit width of columns pending auto-fit to their header and to values of visible rows.
+    fn auto_fit(&mut self, ui: &Ui, text_height: f32, pinned_column_table: bool) {
+        let row_height = text_height + ui.spacing().item_spacing.y;
+        let first_row = (self.scroll_y / row_height) as usize;
+        let last_row = (first_row + self.visible_rows_count + 1).min(self.filtered_nodes.len());
+        let padding = ui.spacing().item_spacing.x * 2.0;
+        let font_id = egui::TextStyle::Body.resolve(ui.style());
+        let text_width = |text: String| ui.fonts(|fonts| fonts.layout_no_wrap(text, font_id.clone(), Color32::PLACEHOLDER).size().x);
+        let columns = if pinned_column_table { &self.column_pinned } else { &self.column_selected };
+        for column in columns.iter().filter(|column| self.auto_fit_columns.contains(&column.name)) {
+            let mut width = text_width(column.name.clone());
+            for table_row_index in first_row.min(last_row)..last_row {
+                let row_data = &self.nodes[self.filtered_nodes[table_row_index]];
+                let value = if column.name.eq("/#") {
+                    Some(row_data.index.to_string())
+                } else {
+                    Self::get_pointer_for_column(&self.parent_pointer, &&row_data.entries, row_data.index, column)
+                        .filter(|entry| !matches!(entry.pointer.value_type, ValueType::Null))
+                        .and_then(|entry| entry.value.as_ref().map(|value| value.replace('\n', "")))
+                };
+                if let Some(value) = value {
+                    width = width.max(text_width(value));
+                }
+            }
+            self.column_widths.insert(column.name.clone(), (width + padding).clamp(*AUTO_FIT_WIDTH_RANGE.start(), *AUTO_FIT_WIDTH_RANGE.end()));
+            self.column_widths_changed = true;
+        }
+        // Remaining columns are fitted when drawing the other table, unless they were removed meanwhile
+        let (columns, other_columns) = if pinned_column_table { (&self.column_pinned, &self.column_selected) } else { (&self.column_selected, &self.column_pinned) };
+        self.auto_fit_columns.retain(|name| !columns.iter().any(|column| column.name.eq(name)) && other_columns.iter().any(|column| column.name.eq(name)));
+    }
+
+    pub fn column_widths(&self) -> &HashMap<String, f32> {
+        &self.column_widths
+    }
+
+    pub fn set_column_widths(&mut self, column_widths: HashMap<String, f32>) {
+        self.column_widths = column_widths;
+    }
+
+    /// Draw a mark in vertical scrollbar gutter for each matching row, so user sees where they are within the whole table.
+    fn paint_matching_rows_markers(&self, ui: &Ui, inner_rect: Rect) {
+        if self.matching_rows.is_empty() || self.filtered_nodes.is_empty() {
+            return;
+        }
+        let spacing = ui.spacing().scroll;
+        let x_range = Rangef::new(inner_rect.right(), inner_rect.right() + spacing.bar_inner_margin + spacing.bar_width);
+        let height = inner_rect.height();
+        let painter = ui.painter();
+        let mut last_y = f32::NEG_INFINITY;
+        for row in self.matching_rows.iter() {
+            let y = (inner_rect.top() + (*row as f32 / self.filtered_nodes.len() as f32) * height).round();
+            if y - last_y < 2.0 {
+                continue;
+            }
+            last_y = y;
+            painter.hline(x_range, y, Stroke::new(2.0, SEARCH_MATCH_COLOR));
+        }
+        let selected_row = self.matching_rows[self.matching_row_selected];
+        let y = (inner_rect.top() + (selected_row as f32 / self.filtered_nodes.len() as f32) * height).round();
+        painter.hline(x_range, y, Stroke::new(3.0, ACTIVE_COLOR));
+    }
+
+    /// Layout value with substrings matching search highlighted, None when nothing matches.
+    fn highlight_search_matches(ui: &Ui, value: &str, regex: &Regex) -> Option<LayoutJob> {
+        let mut matches = regex.find_iter(value).filter(|m| !m.is_empty()).peekable();
+        matches.peek()?;
+        let font_id = egui::TextStyle::Body.resolve(ui.style());
+        let color = ui.visuals().text_color();
+        let mut job = LayoutJob::default();
+        let mut last_end = 0;
+        for m in matches {
+            job.append(&value[last_end..m.start()], 0.0, TextFormat::simple(font_id.clone(), color));
+            job.append(m.as_str(), 0.0, TextFormat { background: SEARCH_MATCH_COLOR, ..TextFormat::simple(font_id.clone(), Color32::BLACK) });
+            last_end = m.end();
+        }
+        job.append(&value[last_end..], 0.0, TextFormat::simple(font_id, color));
+        Some(job)
+    }
+
+    fn header(&mut self, pinned_column_table: bool, array_response: &mut ArrayResponse, mut header: TableRow) {
         // Mutation after interaction
         let mut clicked_filter_non_null_column: Option<String> = None;
         let mut clicked_filter_column_value: Option<(String, String)> = None;
         let mut pinned_column: Option<usize> = None;
+        let mut clicked_column: Option<(usize, bool)> = None;
+        let mut hidden_column: Option<String> = None;
+        let mut moved_column: Option<(String, String)> = None;
+        let mut open_column_chooser = false;
+        let mut profiled_column: Option<Column> = None;
+        let mut jump_to_type: Option<(usize, ValueType)> = None;
+        let mut converted_column: Option<(Column, Conversion)> = None;
+        let mut unnested_column: Option<String> = None;
+        let mut auto_fit_columns: Vec<String> = vec![];
+        let mut toggled_group: Option<String> = None;
+        let column_offset = if pinned_column_table { 0 } else { self.column_pinned.len() };
+        let has_column_groups = self.has_column_groups();
+        let group_runs = self.column_group_runs(pinned_column_table);
         header.cols(true, |ui, index| {
             let columns = if pinned_column_table { &self.column_pinned } else { &self.column_selected };
             let column = columns.get(index).unwrap();
             let name = column.name.clone().to_string();
-            let strong = Label::new(WidgetText::RichText(egui::RichText::from(&name)));
+            let group = self.column_group(&column.name);
+            let display_name = match group {
+                Some(group) if group.eq(&name) => "{…}".to_string(),
+                Some(group) => name[group.len() + 1..].to_string(),
+                None => name.clone()
+            };
+            let strong = Label::new(WidgetText::RichText(egui::RichText::from(&display_name))).sense(Sense::click());
             let label = Label::new(&name);
+            let is_movable = !pinned_column_table && !column.name.is_empty();
             let response = ui.vertical(|ui| {
-                let response = ui.add(strong).on_hover_ui(|ui| { ui.add(label); });
+                if has_column_groups {
+                    let (group_rect, group_response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), ui.spacing().interact_size.y), Sense::click());
+                    if let (Some(group), Some(run)) = (group, group_runs[index]) {
+                        self.paint_column_group(ui, group_rect, group, pinned_column_table, index, run);
+                        if group_response.on_hover_text(group).clicked() && !pinned_column_table {
+                            toggled_group = Some(group.to_string());
+                        }
+                    }
+                }
+                let response = if is_movable {
+                    ui.dnd_drag_source(Id::new("header_column").with(&name), DraggedColumn(name.clone()), |ui| ui.add(strong)).inner
+                } else {
+                    ui.add(strong)
+                };
+                let response = response.on_hover_ui(|ui| { ui.add(label); });
+                if response.clicked() && !column.name.is_empty() {
+                    clicked_column = Some((column_offset + index, ui.input(|i| i.modifiers.shift)));
+                }
+                response.context_menu(|ui| {
+                    if ui.add_enabled(is_movable, Button::new("Hide column")).clicked() {
+                        hidden_column = Some(column.name.clone());
+                        ui.close_menu();
+                    }
+                    if ui.button("Choose columns...").clicked() {
+                        open_column_chooser = true;
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button("Auto-fit column").clicked() {
+                        auto_fit_columns = vec![column.name.clone()];
+                        ui.close_menu();
+                    }
+                    if ui.button("Auto-fit all columns").clicked() {
+                        auto_fit_columns = self.column_pinned.iter().chain(self.column_selected.iter()).map(|column| column.name.clone()).collect();
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.add_enabled(!column.name.is_empty() && !column.name.eq("/#"), Button::new("Column profile")).clicked() {
+                        profiled_column = Some(column.clone());
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled((!self.is_sub_table || self.unnest_path.is_some()) && matches!(column.value_type, ValueType::Array(_)), Button::new("Unnest"))
+                        .on_hover_text("Open a view having one row per element of this array").clicked() {
+                        unnested_column = Some(column.name.clone());
+                        ui.close_menu();
+                    }
+                    ui.add_enabled_ui(!column.name.is_empty() && !column.name.eq("/#"), |ui| {
+                        ui.menu_button("Convert to", |ui| {
+                            for conversion in Conversion::ALL {
+                                if ui.button(conversion.as_str()).clicked() {
+                                    converted_column = Some((column.clone(), conversion));
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    });
+                });
 
                 if !pinned_column_table || index > 0 {
                     ui.horizontal(|ui| {
@@ -530,7 +1914,8 @@ impl ArrayTable {
                                              clicked_filter_non_null_column = Some(name);
                                          }
 
-                                         if Self::is_filterable(column) {
+                                         let is_array = matches!(column.value_type, ValueType::Array(_));
+                                         if Self::is_filterable(column) || is_array {
                                              let mut cache_ref_mut = self.cache.borrow_mut();
                                              let cache = cache_ref_mut.cache::<crate::components::cache::FrameCache<IndexSet<String>, CacheFilterOptions>>();
 
@@ -538,6 +1923,9 @@ impl ArrayTable {
                                              if !values.is_empty() {
                                                  let checked_filtered_values = self.columns_filter.get(&column.name);
                                                  ui.separator();
+                                                 if is_array {
+                                                     ui.label(egui::RichText::new("Array contains").weak());
+                                                 }
                                                  values.iter().for_each(|value| {
                                                      let mut chcked = if let Some(filters) = checked_filtered_values {
                                                          filters.contains(value)
@@ -551,18 +1939,76 @@ impl ArrayTable {
                                              }
                                          }
                                      });
+                        if column.has_mixed_types() {
+                            let types = column.observed_types.iter().map(|value_type| value_type_name(*value_type)).collect::<Vec<&str>>().join(", ");
+                            PopupMenu::new(column_id.with("types"))
+                                .show_ui(ui, |ui| icon::button(ui, TRIANGLE_EXCLAMATION, Some(&format!("Mixed types: {}", types)), Some(SEARCH_MATCH_COLOR)),
+                                         |ui| {
+                                             ui.label("Jump to next row with type");
+                                             for value_type in column.observed_types.iter() {
+                                                 if ui.button(value_type_name(*value_type)).clicked() {
+                                                     jump_to_type = Some((column_offset + index, *value_type));
+                                                 }
+                                             }
+                                         });
+                        }
                     });
                 }
 
                 response
             });
+            if is_movable {
+                if let Some(dragged_column) = response.response.dnd_hover_payload::<DraggedColumn>() {
+                    if !dragged_column.0.eq(&column.name) {
+                        ui.painter().vline(response.response.rect.left(), response.response.rect.y_range(), Stroke::new(2.0, ACTIVE_COLOR));
+                    }
+                }
+                if let Some(dragged_column) = response.response.dnd_release_payload::<DraggedColumn>() {
+                    moved_column = Some((dragged_column.0.clone(), column.name.clone()));
+                }
+            }
             Some(response.inner)
         });
+        if let Some((column, extend)) = clicked_column {
+            self.select_column(column, extend);
+        }
+        if let Some(column) = hidden_column {
+            self.hidden_columns.insert(column);
+            self.update_selected_columns(self.max_depth);
+        }
+        if let Some((column, before)) = moved_column {
+            self.move_column(&column, &before);
+            self.update_selected_columns(self.max_depth);
+        }
+        if open_column_chooser {
+            self.open_column_chooser();
+        }
+        self.auto_fit_columns.extend(auto_fit_columns);
+        if let Some(group) = toggled_group {
+            self.toggle_column_group(group);
+        }
+        if let Some(column) = profiled_column {
+            self.open_column_profile(&column);
+        }
+        if let Some((column, value_type)) = jump_to_type {
+            self.jump_to_next_row_with_type(column, value_type);
+        }
+        if let Some(column) = unnested_column {
+            self.open_unnest(column);
+        }
+        if let Some((column, conversion)) = converted_column {
+            if let Some(edited_value) = self.convert_column(&column, conversion) {
+                array_response.edited_value = Some(edited_value);
+            }
+        }
         if let Some(pinned_column) = pinned_column {
+            self.selection = None;
             if pinned_column_table {
                 let column = self.column_pinned.remove(pinned_column);
                 self.column_selected.push(column);
-                self.column_selected.sort();
+                let mut column_selected = mem::take(&mut self.column_selected);
+                self.sort_by_columns_order(&mut column_selected);
+                self.column_selected = column_selected;
             } else {
                 let column = self.column_selected.remove(pinned_column);
                 self.column_pinned.push(column);
@@ -580,18 +2026,33 @@ impl ArrayTable {
     fn body<'arraytable>(&'arraytable mut self, text_height: f32, pinned_column_table: bool, mut array_response: &mut ArrayResponse, mut request_repaint: bool, body: TableBody) {
         // Mutation after interaction
         let mut subtable = None;
+        let mut record_view_cell = None;
+        let mut raw_json_cell = None;
         let mut focused_cell = None;
         let mut focused_changed = false;
+        let mut clicked_cell: Option<(usize, usize, bool)> = None;
+        let mut clicked_row: Option<(usize, bool)> = None;
+        let mut edit_next_cell: Option<(usize, usize, bool)> = None;
         let mut updated_value: Option<(PointerKey, String)> = None;
         let columns = if pinned_column_table { &self.column_pinned } else { &self.column_selected };
+        let column_offset = if pinned_column_table { 0 } else { self.column_pinned.len() };
         let hovered_row_index = body.rows(text_height, self.filtered_nodes.len(), |mut row| {
             let table_row_index = row.index();
             let row_index = self.filtered_nodes[table_row_index];
             let node = self.nodes().get(row_index);
+            let search_regex = self.search_regex.as_ref().filter(|_| self.matching_rows.binary_search(&table_row_index).is_ok());
 
             if let Some(row_data) = node.as_ref() {
                 row.cols(false, |ui, col_index| {
                     let cell_id = row_index * columns.len() + col_index + if pinned_column_table { self.seed1 } else { self.seed2 };
+                    let cell_violations = self.cell_violations(row_index, &columns[col_index].name);
+                    if !cell_violations.is_empty() {
+                        ui.painter().rect_filled(ui.max_rect(), egui::Rounding::ZERO, INVALID_CELL_COLOR);
+                        if ui.rect_contains_pointer(ui.max_rect()) {
+                            let messages = cell_violations.iter().map(|violation| violation.message.as_str()).collect::<Vec<&str>>();
+                            egui::show_tooltip_text(ui.ctx(), Id::new(cell_id).with("violations"), messages.join("\n"));
+                        }
+                    }
                     let index = {
                         let mut cache_ref_mut = self.cache.borrow_mut();
                         let cache = cache_ref_mut.cache::<crate::components::cache::FrameCache<Option<usize>, CacheGetPointer>>();
@@ -605,18 +2066,56 @@ impl ArrayTable {
                     let mut editing_index = self.editing_index.borrow_mut();
                     if editing_index.is_some() && editing_index.unwrap() == (col_index, row_index, pinned_column_table) {
                         let ref_mut = &mut *self.editing_value.borrow_mut();
-                        let textedit_response = ui.add(TextEdit::singleline(ref_mut));
-                        if textedit_response.lost_focus() || ui.ctx().input(|input| input.key_pressed(Key::Enter)) {
+                        let property_schema = self.schema_validator.as_ref().and_then(|validator| validator.property_schema(&columns[col_index].name));
+                        let (escape_pressed, tab_pressed) = ui.input(|i| (i.key_pressed(Key::Escape), i.key_pressed(Key::Tab).then_some(i.modifiers.shift)));
+                        let mut cancel = escape_pressed;
+                        let is_inline_array = index.map(|index| &row_data.entries()[index])
+                            .is_some_and(|entry| matches!(entry.pointer.value_type, ValueType::Array(_)) && entry.value.as_ref().and_then(|value| self.cached_inline_items(value)).is_some());
+                        let popup_outcome = if let Some(value_editor) = property_schema.and_then(ValueEditor::from_schema) {
+                            Some(popup_editor(ui, Id::new(cell_id).with("value_editor"), &value_editor, ref_mut))
+                        } else if is_inline_array {
+                            Some(array_popup_editor(ui, Id::new(cell_id).with("array_editor"), ref_mut))
+                        } else {
+                            None
+                        };
+                        let commit = if let Some(outcome) = popup_outcome {
+                            match outcome {
+                                EditOutcome::Editing => false,
+                                EditOutcome::Commit => true,
+                                EditOutcome::Cancel => {
+                                    cancel = true;
+                                    false
+                                }
+                            }
+                        } else {
+                            let textedit_response = ui.add(TextEdit::singleline(ref_mut));
+                            let commit = textedit_response.lost_focus() || tab_pressed.is_some() || ui.ctx().input(|input| input.key_pressed(Key::Enter));
+                            if !cancel && !commit {
+                                textedit_response.request_focus();
+                            }
+                            if let Some(backward) = tab_pressed {
+                                edit_next_cell = Some((column_offset + col_index, table_row_index, backward));
+                            }
+                            commit
+                        };
+                        if cancel {
+                            *editing_index = None;
+                            ref_mut.clear();
+                            edit_next_cell = None;
+                        } else if commit {
+                            let mut value_type = index.map_or(columns[col_index].value_type, |index| row_data.entries()[index].pointer.value_type);
+                            // A number out of schema bounds is written as is, then reported as a violation
+                            if let Some(property_schema) = property_schema {
+                                value_type = value_type_for(property_schema, ref_mut).unwrap_or(value_type);
+                            }
                             let pointer = PointerKey {
                                 pointer: Self::pointer_key(&self.parent_pointer, row_index, &columns.get(col_index).as_ref().unwrap().name),
-                                value_type: columns[col_index].value_type,
+                                value_type,
                                 depth: columns[col_index].depth,
                                 index: row_index,
                                 position: 0,
                             };
                             updated_value = Some((pointer, mem::take(ref_mut)))
-                        } else {
-                            textedit_response.request_focus();
                         }
                     } else if let Some(index) = index {
                         let entry = &row_data.entries()[index];
@@ -624,11 +2123,25 @@ impl ArrayTable {
                         let is_object = matches!(entry.pointer.value_type, ValueType::Object(_));
                         if pinned_column_table && col_index == 0 {
                             let label = Label::new(entry.pointer.index.to_string()).sense(Sense::click());
-                            return Some(label.ui(ui));
+                            let response = label.ui(ui);
+                            if response.clicked() {
+                                clicked_row = Some((table_row_index, ui.input(|i| i.modifiers.shift)));
+                            }
+                            return Some(response);
                         } else if let Some(value) = entry.value.as_ref() {
                             if !matches!(entry.pointer.value_type, ValueType::Null) {
+                                let search_regex = search_regex.filter(|_| self.search_options.column.as_ref().is_none_or(|c| c.eq(&columns[col_index].name)));
+                                let highlighted_value = search_regex.filter(|_| !is_array && !is_object).and_then(|regex| Self::highlight_search_matches(ui, value, regex));
+                                let is_matching_cell = highlighted_value.is_some() || search_regex.is_some_and(|regex| self.search_options.match_keys
+                                    && regex.is_match(columns[col_index].name.rsplit('/').next().unwrap_or_default()));
+                                if is_matching_cell {
+                                    ui.painter().rect_stroke(ui.max_rect().shrink(1.0), egui::Rounding::ZERO, Stroke::new(1.5, SEARCH_MATCH_COLOR));
+                                }
+                                let inline_items = if is_array { self.cached_inline_items(value) } else { None };
                                 let mut label = if is_array || is_object {
                                     Label::new(value.replace('\n', "")) // maybe we want cache
+                                } else if let Some(highlighted_value) = highlighted_value {
+                                    Label::new(highlighted_value)
                                 } else {
                                     Label::new(value)
                                 };
@@ -636,12 +2149,19 @@ impl ArrayTable {
                                 let rect = ui.available_rect_before_wrap();
                                 let cell_zone = ui.interact(rect, Id::new(cell_id), Sense::click());
 
-                                label = label.sense(Sense::click());
-                                let mut response = cell_zone.union(label.ui(ui));
+                                let mut response = if let Some(items) = inline_items.as_ref() {
+                                    cell_zone.union(chips(ui, items))
+                                } else {
+                                    label = label.sense(Sense::click());
+                                    cell_zone.union(label.ui(ui))
+                                };
 
                                 let is_array = matches!(entry.pointer.value_type, ValueType::Array(_));
                                 let is_object = matches!(entry.pointer.value_type, ValueType::Object(_));
 
+                                if response.clicked() {
+                                    clicked_cell = Some((column_offset + col_index, table_row_index, ui.input(|i| i.modifiers.shift)));
+                                }
                                 if response.double_clicked() {
                                     *self.editing_value.borrow_mut() = value.clone();
                                     *editing_index = Some((col_index, row_index, pinned_column_table));
@@ -670,6 +2190,14 @@ impl ArrayTable {
                                     }
                                     if !self.is_sub_table {
                                         ui.separator();
+                                        if ui.button("Show row in record view").clicked() {
+                                            ui.close_menu();
+                                            record_view_cell = Some((column_offset + col_index, table_row_index));
+                                        }
+                                        if ui.button("Show row as JSON").clicked() {
+                                            ui.close_menu();
+                                            raw_json_cell = Some((column_offset + col_index, table_row_index));
+                                        }
                                         if ui.button("Open row in sub table".to_string()).clicked() {
                                             ui.close_menu();
                                             let root_node = row_data.entries.last().unwrap();
@@ -685,6 +2213,8 @@ impl ArrayTable {
                                         ui.ctx().copy_text(entry.pointer.pointer.clone());
                                         ui.close_menu();
                                     }
+                                    ui.separator();
+                                    self.copy_rows_menu(ui, column_offset + col_index, table_row_index);
                                 });
 
                                 if let Some((focused_col, focused_row, focused_is_pinned_table)) = self.focused_cell {
@@ -708,8 +2238,11 @@ impl ArrayTable {
                     }
                     let rect = ui.available_rect_before_wrap();
                     let response = ui.interact(rect, Id::new(cell_id), Sense::click());
+                    if response.clicked() {
+                        clicked_cell = Some((column_offset + col_index, table_row_index, ui.input(|i| i.modifiers.shift)));
+                    }
                     if response.double_clicked() {
-                        *self.editing_value.borrow_mut() = String::new();
+                        *self.editing_value.borrow_mut() = self.default_value(&columns[col_index]);
                         *editing_index = Some((col_index, row_index, pinned_column_table));
                     }
 
@@ -719,12 +2252,20 @@ impl ArrayTable {
                     }
                     response.context_menu(|ui| {
                         if ui.button("Edit").clicked() {
-                            *self.editing_value.borrow_mut() = String::new();
+                            *self.editing_value.borrow_mut() = self.default_value(&columns[col_index]);
                             *editing_index = Some((col_index, row_index, pinned_column_table));
                             ui.close_menu();
                         }
                         if !self.is_sub_table {
                             ui.separator();
+                            if ui.button("Show row in record view").clicked() {
+                                ui.close_menu();
+                                record_view_cell = Some((column_offset + col_index, table_row_index));
+                            }
+                            if ui.button("Show row as JSON").clicked() {
+                                ui.close_menu();
+                                raw_json_cell = Some((column_offset + col_index, table_row_index));
+                            }
                             if ui.button("Open row in sub table".to_string()).clicked() {
                                 ui.close_menu();
                                 let root_node = row_data.entries.last().unwrap();
@@ -734,6 +2275,8 @@ impl ArrayTable {
                                 ));
                             }
                         }
+                        ui.separator();
+                        self.copy_rows_menu(ui, column_offset + col_index, table_row_index);
                     });
 
                     if let Some((focused_col, focused_row, focused_is_pinned_table)) = self.focused_cell {
@@ -752,9 +2295,23 @@ impl ArrayTable {
         if focused_changed {
             self.focused_cell = focused_cell;
         }
+        if let Some((column, row, extend)) = clicked_cell {
+            self.select_cell(column, row, extend);
+        }
+        if let Some((row, extend)) = clicked_row {
+            self.select_row(row, extend);
+        }
         if let Some(subtable) = subtable {
             self.windows.push(subtable);
         }
+        if let Some((column, row)) = record_view_cell {
+            self.select_cell(column, row, false);
+            self.show_record_view = true;
+        }
+        if let Some((column, row)) = raw_json_cell {
+            self.select_cell(column, row, false);
+            self.show_raw_json = true;
+        }
         if let Some((pointer, value)) = updated_value {
             let editing_index = mem::take(&mut *self.editing_index.borrow_mut());
             let value = if value.is_empty() {
@@ -788,6 +2345,13 @@ impl ArrayTable {
                 }
             }
         }
+        if let Some((column, row, backward)) = edit_next_cell {
+            if let Some(next_cell) = self.next_editable_cell((column, row), backward) {
+                self.selection = Some(CellSelection::new(next_cell));
+                self.scroll_to_selection_cursor = true;
+                self.edit_cell(next_cell);
+            }
+        }
         if self.hovered_row_index != hovered_row_index {
             self.hovered_row_index = hovered_row_index;
             request_repaint = true;
@@ -806,6 +2370,16 @@ impl ArrayTable {
     }
 
     fn update_value(&mut self, updated_entry: FlatJsonValue<String>, row_index: usize, should_update_subtable: bool) -> bool {
+        // Whole row written, e.g. when undoing a row replacement
+        if !self.is_sub_table && self.nodes[row_index].entries.last().is_some_and(|root| root.pointer.pointer.eq(&updated_entry.pointer.pointer)) {
+            return match self.replace_row(row_index, updated_entry.value.unwrap_or_else(|| "null".to_string())) {
+                Ok(value_changed) => value_changed,
+                Err(e) => {
+                    log!("Failed to replace row {}: {}", row_index, e);
+                    false
+                }
+            };
+        }
         let mut value_changed = false;
         if should_update_subtable {
             for subtable in self.windows.iter_mut() {
@@ -816,19 +2390,30 @@ impl ArrayTable {
             }
         }
 
-        if let Some(entry) = self.nodes[row_index].entries.iter_mut().find(|entry| entry.pointer.pointer.eq(&updated_entry.pointer.pointer)) {
-            if !entry.value.eq(&updated_entry.value) {
+        let row_prefix_len = self.parent_pointer.len() + 1 + self.nodes[row_index].index.to_string().len();
+        if updated_entry.value.is_some() && updated_entry.pointer.pointer.len() > row_prefix_len {
+            self.add_observed_type(&updated_entry.pointer.pointer[row_prefix_len..], updated_entry.pointer.value_type);
+        }
+        if let Some(entry) = Arc::make_mut(&mut self.nodes)[row_index].entries.iter_mut().find(|entry| entry.pointer.pointer.eq(&updated_entry.pointer.pointer)) {
+            if !entry.value.eq(&updated_entry.value) || entry.pointer.value_type != updated_entry.pointer.value_type {
                 value_changed = true;
+                if !self.is_sub_table {
+                    self.undo_step.push(UndoEntry::Value(row_index, entry.clone()));
+                }
                 entry.value = updated_entry.value;
+                entry.pointer.value_type = updated_entry.pointer.value_type;
             }
         } else if updated_entry.value.is_some() {
             value_changed = true;
-            let entries = &mut self.nodes[row_index].entries;
+            if !self.is_sub_table {
+                self.undo_step.push(UndoEntry::Value(row_index, FlatJsonValue { pointer: updated_entry.pointer.clone(), value: None }));
+            }
+            let entries = &mut Arc::make_mut(&mut self.nodes)[row_index].entries;
             entries.insert(entries.len() - 1, FlatJsonValue::<String> { pointer: updated_entry.pointer, value: updated_entry.value });
         }
         // After update we serialized root element then parse it again so nested serialized object are updated aswellgit
         if value_changed && !self.is_sub_table {
-            let root_node = self.nodes[row_index].entries.pop().unwrap();
+            let root_node = Arc::make_mut(&mut self.nodes)[row_index].entries.pop().unwrap();
             let value1 = serialize_to_json_with_option::<String>(
                 &mut self.nodes[row_index].entries.clone(),
                 root_node.pointer.depth + 1);
@@ -838,15 +2423,124 @@ impl ArrayTable {
                                                .prefix(root_node.pointer.pointer.clone())
                                                .start_depth(root_node.pointer.depth + 1).parse_array(false)
                                                .max_depth(self.last_parsed_max_depth)).unwrap().to_owned();
-            let line_number_entry = mem::take(&mut self.nodes[row_index].entries[0]);
-            self.nodes[row_index].entries.clear();
-            self.nodes[row_index].entries.push(line_number_entry);
-            self.nodes[row_index].entries.extend(result.json);
-            self.nodes[row_index].entries.push(FlatJsonValue { pointer: root_node.pointer, value: Some(new_root_node_serialized_json) });
+            let line_number_entry = mem::take(&mut Arc::make_mut(&mut self.nodes)[row_index].entries[0]);
+            Arc::make_mut(&mut self.nodes)[row_index].entries.clear();
+            Arc::make_mut(&mut self.nodes)[row_index].entries.push(line_number_entry);
+            Arc::make_mut(&mut self.nodes)[row_index].entries.extend(result.json);
+            Arc::make_mut(&mut self.nodes)[row_index].entries.push(FlatJsonValue { pointer: root_node.pointer, value: Some(new_root_node_serialized_json) });
+            self.validate_row(row_index);
+        }
+        if value_changed {
+            self.changed_rows.insert(row_index);
         }
         value_changed
     }
 
+    /// Convert every value of column, values are written through update_value so conversion can be undone.
+    fn convert_column(&mut self, column: &Column, conversion: Conversion) -> Option<FlatJsonValue<String>> {
+        let mut updates = vec![];
+        let mut failed_rows = vec![];
+        for (row_index, row) in self.nodes.iter().enumerate() {
+            let Some(entry) = Self::get_pointer_for_column(&self.parent_pointer, &&row.entries, row.index, column) else {
+                continue;
+            };
+            if matches!(entry.pointer.value_type, ValueType::Null) {
+                continue;
+            }
+            let value = entry.value.as_deref().unwrap_or_default();
+            match conversion.convert(entry.pointer.value_type, value) {
+                Some((value_type, new_value)) => {
+                    if value_type != entry.pointer.value_type || new_value.ne(value) {
+                        let mut pointer = entry.pointer.clone();
+                        pointer.value_type = value_type;
+                        updates.push((row_index, FlatJsonValue { pointer, value: Some(new_value) }));
+                    }
+                }
+                None => failed_rows.push(row_index),
+            }
+        }
+        let mut edited_value = None;
+        let mut converted_count = 0;
+        for (row_index, entry) in updates {
+            if self.update_value(entry.clone(), row_index, true) {
+                converted_count += 1;
+                edited_value = Some(entry);
+            }
+        }
+        self.refresh_column_types(&column.name);
+        let had_failed_rows_filter = self.conversion_report.as_ref().is_some_and(|report| report.failed_rows_only);
+        self.conversion_report = Some(ConversionReport { column: column.name.clone(), conversion, converted_count, failed_rows, failed_rows_only: false });
+        if had_failed_rows_filter {
+            self.on_rows_filter_changed();
+        }
+        if edited_value.is_some() {
+            self.refresh_search();
+        }
+        edited_value
+    }
+
+    fn conversion_report_window(&mut self, ctx: &Context) {
+        let Some(report) = self.conversion_report.as_mut() else {
+            return;
+        };
+        let failed_rows_only = report.failed_rows_only;
+        let mut opened = true;
+        report.show(ctx, &mut opened);
+        let filter_changed = report.failed_rows_only != failed_rows_only;
+        if !opened {
+            self.conversion_report = None;
+        }
+        if filter_changed || !opened && failed_rows_only {
+            self.on_rows_filter_changed();
+        }
+    }
+
+    /// Compute again column value type and observed types from rows, e.g. after a conversion.
+    fn refresh_column_types(&mut self, column_name: &str) {
+        let Some(column) = self.all_columns.iter().find(|c| c.name.eq(column_name)).cloned() else {
+            return;
+        };
+        let mut observed_column = Column::new(column.name.clone(), ValueType::Null);
+        for row in self.nodes.iter() {
+            if let Some(entry) = Self::get_pointer_for_column(&self.parent_pointer, &&row.entries, row.index, &column) {
+                observed_column.add_observed_type(entry.pointer.value_type);
+            }
+        }
+        let value_type = observed_column.observed_types.iter().find(|value_type| !matches!(value_type, ValueType::Null)).copied().unwrap_or(ValueType::Null);
+        for column in self.all_columns.iter_mut().chain(self.column_selected.iter_mut()).chain(self.column_pinned.iter_mut()) {
+            if column.name.eq(column_name) {
+                column.value_type = value_type;
+                column.observed_types = observed_column.observed_types.clone();
+            }
+        }
+    }
+
+    fn add_observed_type(&mut self, column_name: &str, value_type: ValueType) {
+        for column in self.all_columns.iter_mut().chain(self.column_selected.iter_mut()).chain(self.column_pinned.iter_mut()) {
+            if column.name.eq(column_name) {
+                column.add_observed_type(value_type);
+            }
+        }
+    }
+
+    /// Select next row after selection cursor having a value of `value_type` in column (selection coordinates), wrapping to first row.
+    fn jump_to_next_row_with_type(&mut self, column: usize, value_type: ValueType) {
+        let Some(column_data) = self.column_at(column) else {
+            return;
+        };
+        let rows_count = self.filtered_nodes.len();
+        let start = self.selection.map_or(0, |selection| selection.cursor.1 + 1);
+        let found = (0..rows_count).map(|i| (start + i) % rows_count).find(|table_row_index| {
+            let row = &self.nodes[self.filtered_nodes[*table_row_index]];
+            Self::get_pointer_for_column(&self.parent_pointer, &&row.entries, row.index, column_data)
+                .is_some_and(|entry| mem::discriminant(&entry.pointer.value_type) == mem::discriminant(&value_type))
+        });
+        if let Some(table_row_index) = found {
+            self.select_cell(column, table_row_index, false);
+            self.scroll_to_selection_cursor = true;
+        }
+    }
+
     // C
 
     #[inline]
@@ -869,7 +2563,7 @@ impl ArrayTable {
     }
 
     #[inline]
-    fn get_pointer_for_column<'a>(parent_pointer: &String, data: &&'a Vec<FlatJsonValue<String>>, row_index: usize, column: &Column) -> Option<&'a FlatJsonValue<String>> {
+    pub(crate) fn get_pointer_for_column<'a>(parent_pointer: &String, data: &&'a Vec<FlatJsonValue<String>>, row_index: usize, column: &Column) -> Option<&'a FlatJsonValue<String>> {
         let key = &column.name;
         let key = Self::pointer_key(parent_pointer, row_index, key);
         return data.iter().find(|entry| {
@@ -897,12 +2591,154 @@ impl ArrayTable {
         } else {
             self.columns_filter.insert(column, vec![value]);
         }
+        self.refresh_filtered_nodes();
+        self.next_frame_reset_scroll = true;
+        self.selection = None;
+        self.matching_rows.clear();
+        self.matching_row_selected = 0;
+        self.refresh_search();
+    }
+
+    fn refresh_filtered_nodes(&mut self) {
         if self.columns_filter.is_empty() {
             self.filtered_nodes = (0..self.nodes.len()).collect::<Vec<usize>>();
         } else {
             self.filtered_nodes = crate::parser::filter_columns(&self.nodes, &self.parent_pointer, &self.columns_filter);
         }
+        if self.invalid_rows_only {
+            self.filtered_nodes.retain(|row_index| self.violations.contains_key(row_index));
+        }
+        if let Some(report) = self.conversion_report.as_ref().filter(|report| report.failed_rows_only) {
+            self.filtered_nodes.retain(|row_index| report.failed_rows.binary_search(row_index).is_ok());
+        }
+    }
+
+    /// Refresh filtered rows after a filter not related to columns values changed.
+    fn on_rows_filter_changed(&mut self) {
+        self.refresh_filtered_nodes();
         self.next_frame_reset_scroll = true;
+        self.selection = None;
+        self.matching_rows.clear();
+        self.matching_row_selected = 0;
+        self.refresh_search();
+    }
+
+    /// Validate every row against `schema`, then keep validating rows when they are updated.
+    pub fn attach_schema(&mut self, schema: serde_json::Value) -> Result<(), String> {
+        self.schema_validator = Some(SchemaValidator::new(schema)?);
+        self.violations.clear();
+        for row_index in 0..self.nodes.len() {
+            self.validate_row(row_index);
+        }
+        self.show_violations = true;
+        if self.invalid_rows_only {
+            self.refresh_filtered_nodes();
+        }
+        Ok(())
+    }
+
+    pub fn detach_schema(&mut self) {
+        self.schema_validator = None;
+        self.violations.clear();
+        self.show_violations = false;
+        if self.invalid_rows_only {
+            self.set_invalid_rows_only(false);
+        }
+    }
+
+    pub fn has_schema(&self) -> bool {
+        self.schema_validator.is_some()
+    }
+
+    fn validate_row(&mut self, row_index: usize) {
+        let Some(ref validator) = self.schema_validator else {
+            return;
+        };
+        let violations = match serde_json::from_str::<serde_json::Value>(self.row_json(row_index)) {
+            Ok(row) => validator.validate_row(&row),
+            Err(e) => vec![Violation { pointer: String::new(), message: format!("row is not valid json: {}", e) }],
+        };
+        if violations.is_empty() {
+            self.violations.remove(&row_index);
+        } else {
+            self.violations.insert(row_index, violations);
+        }
+    }
+
+    /// Violations displayed in cell of `column` for row at `row_index`.
+    fn cell_violations(&self, row_index: usize, column: &str) -> Vec<&Violation> {
+        self.violations.get(&row_index)
+            .map_or(vec![], |violations| violations.iter().filter(|violation| violation.matches_column(column)).collect())
+    }
+
+    fn set_invalid_rows_only(&mut self, invalid_rows_only: bool) {
+        self.invalid_rows_only = invalid_rows_only;
+        self.on_rows_filter_changed();
+    }
+
+    /// Select cell displaying `pointer`, relative to row, or whole row when no column displays it.
+    fn go_to_pointer(&mut self, row_index: usize, pointer: &str) {
+        let Some(table_row_index) = self.filtered_nodes.iter().position(|i| *i == row_index) else {
+            return;
+        };
+        let violation = Violation { pointer: pointer.to_string(), message: String::new() };
+        // Nested column is preferred over its parent object column
+        let column = (0..self.columns_count())
+            .filter(|i| self.column_at(*i).is_some_and(|column| violation.matches_column(&column.name)))
+            .max_by_key(|i| self.column_at(*i).map_or(0, |column| column.name.len()));
+        match column {
+            Some(column) => self.select_cell(column, table_row_index, false),
+            None => self.select_row(table_row_index, false),
+        }
+        self.scroll_to_selection_cursor = true;
+    }
+
+    fn violations_panel(&mut self, ui: &mut Ui) {
+        let mut go_to = None;
+        let mut invalid_rows_only = self.invalid_rows_only;
+        ui.horizontal(|ui| {
+            ui.strong("Schema violations");
+            ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Close").clicked() {
+                    self.show_violations = false;
+                }
+            });
+        });
+        ui.label(format!("{} invalid rows", self.violations.len()));
+        if let Some(ignored_keywords) = self.schema_validator.as_ref().map(|validator| validator.ignored_keywords()).filter(|keywords| !keywords.is_empty()) {
+            let ignored_keywords = ignored_keywords.iter().map(String::as_str).collect::<Vec<&str>>().join(", ");
+            ui.add(Label::new(egui::RichText::new(format!("Ignored keywords: {}", ignored_keywords)).color(ui.visuals().warn_fg_color)).truncate(true))
+                .on_hover_text(format!("Schema keywords not supported by validator, values are not checked against them: {}", ignored_keywords));
+        }
+        ui.checkbox(&mut invalid_rows_only, "Invalid rows only");
+        ui.separator();
+        let text_height = Self::row_height(ui.style(), ui.spacing());
+        let violations_count = self.violations.values().map(|violations| violations.len()).sum();
+        egui::ScrollArea::vertical().auto_shrink([false, false]).show_rows(ui, text_height, violations_count, |ui, range| {
+            let violations = self.violations.iter()
+                .flat_map(|(row_index, violations)| violations.iter().map(move |violation| (*row_index, violation)))
+                .skip(range.start).take(range.len());
+            for (row_index, violation) in violations {
+                let pointer = if violation.pointer.is_empty() { "row" } else { violation.pointer.as_str() };
+                let is_displayed = self.filtered_nodes.binary_search(&row_index).is_ok();
+                let response = ui.add_enabled(is_displayed, Label::new(format!("#{} {}: {}", self.nodes[row_index].index, pointer, violation.message))
+                    .truncate(true).sense(Sense::click()))
+                    .on_hover_text(&violation.message)
+                    .on_disabled_hover_text("Row is filtered out");
+                if response.clicked() {
+                    go_to = Some((row_index, violation.pointer.clone()));
+                }
+                if response.hovered() {
+                    ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+                }
+            }
+        });
+        if invalid_rows_only != self.invalid_rows_only {
+            self.set_invalid_rows_only(invalid_rows_only);
+        }
+        if let Some((row_index, pointer)) = go_to {
+            self.go_to_pointer(row_index, &pointer);
+        }
     }
 
     #[inline]
@@ -913,7 +2749,56 @@ impl ArrayTable {
     pub fn reset_search(&mut self) {
         self.scroll_to_row.clear();
         self.matching_rows.clear();
+        self.search_error = None;
+        self.search_regex = None;
         self.changed_scroll_to_row_value = Some(crate::compatibility::now().sub(Duration::from_millis(1000)));
         self.matching_row_selected = 0;
     }
+
+    /// Run search again without waiting for typing debounce, e.g. when search options or filters change.
+    pub fn refresh_search(&mut self) {
+        if !self.scroll_to_row.is_empty() && matches!(self.scroll_to_row_mode, ScrollToRowMode::MatchingTerm) {
+            self.changed_scroll_to_row_value = Some(crate::compatibility::now().sub(Duration::from_millis(1000)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_cell_selection() {
+        let selection = CellSelection::new((2, 4));
+        assert_eq!(selection.columns(), 2..=2);
+        assert_eq!(selection.rows(), 4..=4);
+    }
+
+    #[test]
+    fn reversed_selection_ranges_are_ordered() {
+        let selection = CellSelection { anchor: (3, 5), cursor: (1, 2) };
+        assert_eq!(selection.columns(), 1..=3);
+        assert_eq!(selection.rows(), 2..=5);
+        let selection = CellSelection { anchor: (1, 5), cursor: (3, 2) };
+        assert_eq!(selection.columns(), 1..=3);
+        assert_eq!(selection.rows(), 2..=5);
+    }
+
+    fn table_with_columns(rows_count: usize, columns: &[&str]) -> ArrayTable {
+        let nodes = (0..rows_count).map(|index| JsonArrayEntries { entries: vec![], index }).collect();
+        let columns = columns.iter().map(|name| Column::new(name.to_string(), ValueType::String)).collect();
+        ArrayTable::new(None, nodes, columns, 1, String::new())
+    }
+
+    #[test]
+    fn tab_moves_to_next_editable_cell() {
+        // Row number column is pinned at index 0, then /a and /b
+        let table = table_with_columns(2, &["/a", "/b"]);
+        assert_eq!(table.next_editable_cell((1, 0), false), Some((2, 0)));
+        // Row number column is skipped when wrapping to next or previous row
+        assert_eq!(table.next_editable_cell((2, 0), false), Some((1, 1)));
+        assert_eq!(table.next_editable_cell((1, 1), true), Some((2, 0)));
+        assert_eq!(table.next_editable_cell((2, 1), false), None);
+        assert_eq!(table.next_editable_cell((1, 0), true), None);
+    }
 }
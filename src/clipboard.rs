@@ -0,0 +1,165 @@
+/// Tab separated values as spreadsheets write them: a field containing tab, new line or double quote is quoted,
+/// with inner double quotes doubled.
+pub fn to_tsv(rows: &[Vec<String>]) -> String {
+    let mut tsv = String::new();
+    for row in rows {
+        for (i, field) in row.iter().enumerate() {
+            if i > 0 {
+                tsv.push('\t');
+            }
+            if field.contains(['\t', '\n', '\r', '"']) {
+                tsv.push('"');
+                tsv.push_str(&field.replace('"', "\"\""));
+                tsv.push('"');
+            } else {
+                tsv.push_str(field);
+            }
+        }
+        tsv.push('\n');
+    }
+    tsv
+}
+
+/// Parse tab separated values copied from Excel or Google Sheets, handling quoted fields.
+pub fn parse_tsv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut field_start = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field_start => {
+                // Quoted field, ends at next lone double quote
+                while let Some(c) = chars.next() {
+                    if c == '"' {
+                        if chars.peek() == Some(&'"') {
+                            chars.next();
+                            field.push('"');
+                        } else {
+                            break;
+                        }
+                    } else {
+                        field.push(c);
+                    }
+                }
+                field_start = false;
+            }
+            '\t' => {
+                row.push(std::mem::take(&mut field));
+                field_start = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+                field_start = true;
+            }
+            _ => {
+                field.push(c);
+                field_start = false;
+            }
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Markdown table with `header` as first row, pipes are escaped and new lines removed so each row stays on one line.
+pub fn to_markdown_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    fn escape(value: &str) -> String {
+        value.replace('|', "\\|").replace(['\n', '\r'], "")
+    }
+    let mut markdown = String::new();
+    markdown.push('|');
+    for name in header {
+        markdown.push(' ');
+        markdown.push_str(&escape(name));
+        markdown.push_str(" |");
+    }
+    markdown.push_str("\n|");
+    for _ in header {
+        markdown.push_str(" --- |");
+    }
+    markdown.push('\n');
+    for row in rows {
+        markdown.push('|');
+        for value in row {
+            markdown.push(' ');
+            markdown.push_str(&escape(value));
+            markdown.push_str(" |");
+        }
+        markdown.push('\n');
+    }
+    markdown
+}
+
+/// When clipboard contains a json object or an array of objects, returns each object serialized on one line.
+pub fn parse_json_rows(text: &str) -> Option<Vec<String>> {
+    let text = text.trim();
+    if !text.starts_with('{') && !text.starts_with('[') {
+        return None;
+    }
+    match serde_json::from_str::<serde_json::Value>(text).ok()? {
+        object @ serde_json::Value::Object(_) => Some(vec![object.to_string()]),
+        serde_json::Value::Array(array) if !array.is_empty() && array.iter().all(|value| value.is_object()) => {
+            Some(array.into_iter().map(|value| value.to_string()).collect())
+        }
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter().map(|row| row.iter().map(|field| field.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn parse_plain_fields() {
+        assert_eq!(parse_tsv("a\tb\n1\t2\n"), rows(&[&["a", "b"], &["1", "2"]]));
+        assert_eq!(parse_tsv("a\tb\r\n1\t2"), rows(&[&["a", "b"], &["1", "2"]]));
+        assert_eq!(parse_tsv("a\t\tc\n"), rows(&[&["a", "", "c"]]));
+        assert!(parse_tsv("").is_empty());
+    }
+
+    #[test]
+    fn parse_quoted_fields() {
+        assert_eq!(parse_tsv("\"a \"\"b\"\"\"\tc\n"), rows(&[&["a \"b\"", "c"]]));
+        assert_eq!(parse_tsv("\"tab\there\"\t\"new\nline\"\n"), rows(&[&["tab\there", "new\nline"]]));
+        assert_eq!(parse_tsv("\"\"\tx\n"), rows(&[&["", "x"]]));
+        // Quote not at field start is kept as is
+        assert_eq!(parse_tsv("5\" screen\n"), rows(&[&["5\" screen"]]));
+    }
+
+    #[test]
+    fn parse_ragged_rows() {
+        assert_eq!(parse_tsv("a\tb\tc\n1\n2\t3\n"), rows(&[&["a", "b", "c"], &["1"], &["2", "3"]]));
+        assert_eq!(parse_tsv("a\t\n"), rows(&[&["a", ""]]));
+    }
+
+    #[test]
+    fn tsv_round_trip() {
+        let values = rows(&[&["a\tb", "say \"hi\"", "multi\nline"], &["", "plain", "x"]]);
+        assert_eq!(parse_tsv(&to_tsv(&values)), values);
+    }
+
+    #[test]
+    fn markdown_table_escapes_pipes_and_new_lines() {
+        assert_eq!(to_markdown_table(&["a|b", "c"], &rows(&[&["1|2", "x\ny"]])), "| a\\|b | c |\n| --- | --- |\n| 1\\|2 | xy |\n");
+    }
+
+    #[test]
+    fn json_rows_from_object_or_array_of_objects() {
+        assert_eq!(parse_json_rows(" {\"a\": 1} "), Some(vec!["{\"a\":1}".to_string()]));
+        assert_eq!(parse_json_rows("[{\"a\":1},{\"b\":2}]"), Some(vec!["{\"a\":1}".to_string(), "{\"b\":2}".to_string()]));
+        assert_eq!(parse_json_rows("[1, 2]"), None);
+        assert_eq!(parse_json_rows("[]"), None);
+        assert_eq!(parse_json_rows("a\tb"), None);
+    }
+}
@@ -0,0 +1,78 @@
+use egui::{Context, CursorIcon, Id, Label, ScrollArea, Sense, Stroke, TextEdit};
+
+use crate::array_table::Column;
+use crate::ACTIVE_COLOR;
+
+/// Drag and drop payload used to reorder columns, from column chooser or from table header.
+pub struct DraggedColumn(pub String);
+
+pub enum ColumnChooserAction {
+    None,
+    Toggle(String),
+    ShowAll,
+    HideAll,
+    // Move column before another one
+    Move(String, String),
+    Reset,
+}
+
+#[derive(Default)]
+pub struct ColumnChooserWindow {
+    search: String,
+}
+
+impl ColumnChooserWindow {
+    /// `columns` are in display order, along with their visibility.
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, columns: &[(&Column, bool)]) -> ColumnChooserAction {
+        let mut action = ColumnChooserAction::None;
+        egui::Window::new("Columns")
+            .open(open)
+            .default_width(300.0)
+            .default_height(500.0)
+            .resizable([true, true])
+            .show(ctx, |ui| {
+                ui.add(TextEdit::singleline(&mut self.search).hint_text("Search column").desired_width(f32::INFINITY));
+                ui.horizontal(|ui| {
+                    if ui.button("Show all").clicked() {
+                        action = ColumnChooserAction::ShowAll;
+                    }
+                    if ui.button("Hide all").clicked() {
+                        action = ColumnChooserAction::HideAll;
+                    }
+                    if ui.button("Reset").on_hover_text("Show all columns in default order").clicked() {
+                        action = ColumnChooserAction::Reset;
+                    }
+                });
+                ui.separator();
+                let search = self.search.to_lowercase();
+                ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    for (column, visible) in columns.iter() {
+                        if !search.is_empty() && !column.name.to_lowercase().contains(&search) {
+                            continue;
+                        }
+                        let response = ui.horizontal(|ui| {
+                            // Reordering is only meaningful when whole list is displayed
+                            if search.is_empty() {
+                                ui.dnd_drag_source(Id::new("column_chooser").with(&column.name), DraggedColumn(column.name.clone()), |ui| {
+                                    ui.add(Label::new("☰").sense(Sense::hover()));
+                                }).response.on_hover_cursor(CursorIcon::Grab);
+                            }
+                            let mut checked = *visible;
+                            if ui.checkbox(&mut checked, column.name.as_str()).changed() {
+                                action = ColumnChooserAction::Toggle(column.name.clone());
+                            }
+                        }).response;
+                        if let Some(dragged_column) = response.dnd_hover_payload::<DraggedColumn>() {
+                            if !dragged_column.0.eq(&column.name) {
+                                ui.painter().hline(response.rect.x_range(), response.rect.top(), Stroke::new(2.0, ACTIVE_COLOR));
+                            }
+                        }
+                        if let Some(dragged_column) = response.dnd_release_payload::<DraggedColumn>() {
+                            action = ColumnChooserAction::Move(dragged_column.0.clone(), column.name.clone());
+                        }
+                    }
+                });
+            });
+        action
+    }
+}
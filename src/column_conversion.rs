@@ -0,0 +1,120 @@
+use egui::{Color32, Context, RichText};
+use json_flat_parser::ValueType;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Conversion {
+    Number,
+    Boolean,
+    String,
+    NullIfEmpty,
+}
+
+impl Conversion {
+    pub const ALL: [Conversion; 4] = [Conversion::Number, Conversion::Boolean, Conversion::String, Conversion::NullIfEmpty];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::String => "string",
+            Self::NullIfEmpty => "null if empty",
+        }
+    }
+
+    /// Converted value along with its type, None when value can not be converted.
+    pub fn convert(&self, value_type: ValueType, value: &str) -> Option<(ValueType, String)> {
+        let trimmed = value.trim();
+        match (self, value_type) {
+            (Self::Number, ValueType::Number) => Some((ValueType::Number, value.to_string())),
+            (Self::Number, ValueType::String) => serde_json::from_str::<serde_json::Number>(trimmed).ok()
+                .map(|_| (ValueType::Number, trimmed.to_string())),
+            (Self::Boolean, ValueType::Bool) => Some((ValueType::Bool, value.to_string())),
+            (Self::Boolean, ValueType::String | ValueType::Number) => match trimmed.to_lowercase().as_str() {
+                "true" | "1" | "yes" => Some((ValueType::Bool, "true".to_string())),
+                "false" | "0" | "no" => Some((ValueType::Bool, "false".to_string())),
+                _ => None
+            },
+            (Self::String, ValueType::String | ValueType::Number | ValueType::Bool) => Some((ValueType::String, value.to_string())),
+            (Self::NullIfEmpty, ValueType::String) if trimmed.is_empty() => Some((ValueType::Null, "null".to_string())),
+            (Self::NullIfEmpty, _) => Some((value_type, value.to_string())),
+            _ => None
+        }
+    }
+}
+
+/// Result of last column conversion, rows which failed conversion can be filtered.
+pub struct ConversionReport {
+    pub column: String,
+    pub conversion: Conversion,
+    pub converted_count: usize,
+    // Sorted rows index
+    pub failed_rows: Vec<usize>,
+    pub failed_rows_only: bool,
+}
+
+impl ConversionReport {
+    pub fn show(&mut self, ctx: &Context, open: &mut bool) {
+        egui::Window::new("Column conversion")
+            .open(open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("{} values of {} converted to {}", self.converted_count, self.column, self.conversion.as_str()));
+                if self.failed_rows.is_empty() {
+                    ui.label("All values have been converted");
+                } else {
+                    ui.label(RichText::new(format!("{} values could not be converted", self.failed_rows.len())).color(Color32::DARK_RED));
+                    ui.checkbox(&mut self.failed_rows_only, "Show failed rows only");
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn converted(conversion: Conversion, value_type: ValueType, value: &str) -> Option<String> {
+        conversion.convert(value_type, value).map(|(_, value)| value)
+    }
+
+    #[test]
+    fn number_from_string() {
+        assert!(matches!(Conversion::Number.convert(ValueType::String, " 42 "), Some((ValueType::Number, value)) if value == "42"));
+        assert_eq!(converted(Conversion::Number, ValueType::String, "-1.5e3"), Some("-1.5e3".to_string()));
+        assert_eq!(converted(Conversion::Number, ValueType::Number, "12"), Some("12".to_string()));
+    }
+
+    #[test]
+    fn number_failures() {
+        assert!(Conversion::Number.convert(ValueType::String, "abc").is_none());
+        assert!(Conversion::Number.convert(ValueType::String, "").is_none());
+        assert!(Conversion::Number.convert(ValueType::String, "1,5").is_none());
+        assert!(Conversion::Number.convert(ValueType::Bool, "true").is_none());
+    }
+
+    #[test]
+    fn boolean_from_string_and_number() {
+        assert!(matches!(Conversion::Boolean.convert(ValueType::String, "Yes"), Some((ValueType::Bool, value)) if value == "true"));
+        assert_eq!(converted(Conversion::Boolean, ValueType::String, " FALSE "), Some("false".to_string()));
+        assert_eq!(converted(Conversion::Boolean, ValueType::Number, "0"), Some("false".to_string()));
+        assert_eq!(converted(Conversion::Boolean, ValueType::Number, "1"), Some("true".to_string()));
+        assert!(Conversion::Boolean.convert(ValueType::String, "maybe").is_none());
+        assert!(Conversion::Boolean.convert(ValueType::Number, "2").is_none());
+    }
+
+    #[test]
+    fn string_from_scalars() {
+        assert!(matches!(Conversion::String.convert(ValueType::Number, "42"), Some((ValueType::String, value)) if value == "42"));
+        assert!(matches!(Conversion::String.convert(ValueType::Bool, "true"), Some((ValueType::String, value)) if value == "true"));
+        assert!(Conversion::String.convert(ValueType::Object(true), "{}").is_none());
+        assert!(Conversion::String.convert(ValueType::Array(0), "[]").is_none());
+    }
+
+    #[test]
+    fn null_if_empty() {
+        assert!(matches!(Conversion::NullIfEmpty.convert(ValueType::String, ""), Some((ValueType::Null, value)) if value == "null"));
+        assert!(matches!(Conversion::NullIfEmpty.convert(ValueType::String, "  "), Some((ValueType::Null, value)) if value == "null"));
+        assert!(matches!(Conversion::NullIfEmpty.convert(ValueType::String, "x"), Some((ValueType::String, value)) if value == "x"));
+        assert!(matches!(Conversion::NullIfEmpty.convert(ValueType::Number, "3"), Some((ValueType::Number, value)) if value == "3"));
+    }
+}
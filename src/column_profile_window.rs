@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use egui::{Color32, Context, Grid, RichText, Sense, Stroke, Ui, Vec2};
+use json_flat_parser::{JsonArrayEntries, ValueType};
+
+use crate::ACTIVE_COLOR;
+use crate::array_table::{ArrayTable, Column};
+
+const TOP_VALUES_COUNT: usize = 10;
+const HISTOGRAM_BINS: usize = 20;
+// Below this number of rows profile is computed on UI thread
+const BACKGROUND_THRESHOLD: usize = 50_000;
+
+pub fn value_type_name(value_type: ValueType) -> &'static str {
+    match value_type {
+        ValueType::Array(_) => "array",
+        ValueType::Object(_) => "object",
+        ValueType::Number => "number",
+        ValueType::String => "string",
+        ValueType::Bool => "boolean",
+        _ => "null",
+    }
+}
+
+#[derive(Default)]
+pub struct NumberStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub histogram: Vec<usize>,
+}
+
+#[derive(Default)]
+pub struct ColumnProfile {
+    pub rows_count: usize,
+    pub missing_count: usize,
+    pub null_count: usize,
+    pub distinct_count: usize,
+    pub types: Vec<(&'static str, usize)>,
+    pub numbers: Option<NumberStats>,
+    pub shortest_string: Option<String>,
+    pub longest_string: Option<String>,
+    pub top_values: Vec<(String, usize)>,
+}
+
+impl ColumnProfile {
+    /// Values of `column`, one item per row: None when row does not have the column.
+    pub fn column_values(rows: &[JsonArrayEntries<String>], parent_pointer: &String, column: &Column) -> Vec<Option<(ValueType, Option<String>)>> {
+        rows.iter().map(|row| {
+            ArrayTable::get_pointer_for_column(parent_pointer, &&row.entries, row.index, column)
+                .map(|entry| (entry.pointer.value_type, entry.value.clone()))
+        }).collect()
+    }
+
+    /// `values` has one item per row: None when row does not have the column.
+    pub fn compute(values: Vec<Option<(ValueType, Option<String>)>>) -> Self {
+        let rows_count = values.len();
+        // Missing values are counted by row rather than from `Column::seen_count`, which is not kept up to date
+        // when a missing cell is edited, and which does not count first occurrence of a column at first parse.
+        let missing_count = values.iter().filter(|value| value.is_none()).count();
+        let mut types: Vec<(&'static str, usize)> = vec![];
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        let mut numbers: Vec<f64> = vec![];
+        // String along with its count of chars
+        let mut shortest_string: Option<(&str, usize)> = None;
+        let mut longest_string: Option<(&str, usize)> = None;
+        let mut null_count = 0;
+        for (value_type, value) in values.iter().flatten() {
+            let type_name = value_type_name(*value_type);
+            if let Some(count) = types.iter_mut().find(|(name, _)| *name == type_name) {
+                count.1 += 1;
+            } else {
+                types.push((type_name, 1));
+            }
+            let Some(value) = value.as_deref().filter(|_| !matches!(value_type, ValueType::Null)) else {
+                null_count += 1;
+                continue;
+            };
+            *occurrences.entry(value).or_default() += 1;
+            match value_type {
+                ValueType::Number => {
+                    if let Ok(number) = value.parse::<f64>() {
+                        numbers.push(number);
+                    }
+                }
+                ValueType::String => {
+                    let length = value.chars().count();
+                    if shortest_string.is_none_or(|(_, shortest)| length < shortest) {
+                        shortest_string = Some((value, length));
+                    }
+                    if longest_string.is_none_or(|(_, longest)| length > longest) {
+                        longest_string = Some((value, length));
+                    }
+                }
+                _ => {}
+            }
+        }
+        types.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        let mut top_values = occurrences.iter().map(|(value, count)| (value.to_string(), *count)).collect::<Vec<(String, usize)>>();
+        top_values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_values.truncate(TOP_VALUES_COUNT);
+        Self {
+            rows_count,
+            missing_count,
+            null_count,
+            distinct_count: occurrences.len(),
+            types,
+            numbers: Self::number_stats(numbers),
+            shortest_string: shortest_string.map(|(s, _)| s.to_string()),
+            longest_string: longest_string.map(|(s, _)| s.to_string()),
+            top_values,
+        }
+    }
+
+    fn number_stats(mut numbers: Vec<f64>) -> Option<NumberStats> {
+        if numbers.is_empty() {
+            return None;
+        }
+        numbers.sort_by(|a, b| a.total_cmp(b));
+        let min = numbers[0];
+        let max = numbers[numbers.len() - 1];
+        let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
+        let middle = numbers.len() / 2;
+        let median = if numbers.len().is_multiple_of(2) { (numbers[middle - 1] + numbers[middle]) / 2.0 } else { numbers[middle] };
+        let mut histogram = vec![0; HISTOGRAM_BINS];
+        let range = max - min;
+        for number in numbers.iter() {
+            let bin = if range > 0.0 { (((number - min) / range) * HISTOGRAM_BINS as f64) as usize } else { 0 };
+            histogram[bin.min(HISTOGRAM_BINS - 1)] += 1;
+        }
+        Some(NumberStats { min, max, mean, median, histogram })
+    }
+}
+
+pub struct ColumnProfileWindow {
+    column: String,
+    profile: Arc<Mutex<Option<ColumnProfile>>>,
+}
+
+impl ColumnProfileWindow {
+    /// `values` are extracted from rows by caller, so rows are not shared with profile computation,
+    /// which runs in background for large tables.
+    pub fn new(column: String, values: Vec<Option<(ValueType, Option<String>)>>) -> Self {
+        let profile = Arc::new(Mutex::new(None));
+        #[cfg(not(target_arch = "wasm32"))]
+        if values.len() > BACKGROUND_THRESHOLD {
+            let profile_ref = profile.clone();
+            std::thread::spawn(move || {
+                let computed_profile = ColumnProfile::compute(values);
+                *profile_ref.lock().unwrap() = Some(computed_profile);
+            });
+            return Self { column, profile };
+        }
+        *profile.lock().unwrap() = Some(ColumnProfile::compute(values));
+        Self { column, profile }
+    }
+
+    pub fn column(&self) -> &String {
+        &self.column
+    }
+
+    pub fn show(&self, ctx: &Context, open: &mut bool) {
+        egui::Window::new(format!("Profile {}", self.column))
+            .id(egui::Id::new("column_profile").with(&self.column))
+            .open(open)
+            .default_width(380.0)
+            .resizable([true, true])
+            .show(ctx, |ui| {
+                let profile = self.profile.lock().unwrap();
+                let Some(profile) = profile.as_ref() else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Computing profile...");
+                    });
+                    ctx.request_repaint();
+                    return;
+                };
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    Self::profile_ui(ui, profile);
+                });
+            });
+    }
+
+    fn profile_ui(ui: &mut Ui, profile: &ColumnProfile) {
+        Grid::new("column_profile_summary").num_columns(2).striped(true).show(ui, |ui| {
+            ui.label("Rows");
+            ui.label(profile.rows_count.to_string());
+            ui.end_row();
+            ui.label("Missing");
+            ui.label(percentage(profile.missing_count, profile.rows_count));
+            ui.end_row();
+            ui.label("Null");
+            ui.label(percentage(profile.null_count, profile.rows_count));
+            ui.end_row();
+            ui.label("Distinct values");
+            ui.label(profile.distinct_count.to_string());
+            ui.end_row();
+            for (type_name, count) in profile.types.iter() {
+                ui.label(format!("Type {}", type_name));
+                ui.label(percentage(*count, profile.rows_count));
+                ui.end_row();
+            }
+            if let Some(ref numbers) = profile.numbers {
+                ui.label("Min");
+                ui.label(numbers.min.to_string());
+                ui.end_row();
+                ui.label("Max");
+                ui.label(numbers.max.to_string());
+                ui.end_row();
+                ui.label("Mean");
+                ui.label(format!("{:.4}", numbers.mean));
+                ui.end_row();
+                ui.label("Median");
+                ui.label(numbers.median.to_string());
+                ui.end_row();
+            }
+            if let Some(ref shortest_string) = profile.shortest_string {
+                ui.label("Shortest string");
+                ui.label(shortest_string);
+                ui.end_row();
+            }
+            if let Some(ref longest_string) = profile.longest_string {
+                ui.label("Longest string");
+                ui.add(egui::Label::new(longest_string).truncate(true)).on_hover_text(longest_string);
+                ui.end_row();
+            }
+        });
+        if let Some(ref numbers) = profile.numbers {
+            ui.separator();
+            ui.label(RichText::new("Histogram").strong());
+            Self::histogram_ui(ui, &numbers.histogram);
+            ui.horizontal(|ui| {
+                ui.label(numbers.min.to_string());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| ui.label(numbers.max.to_string()));
+            });
+        }
+        if !profile.top_values.is_empty() {
+            ui.separator();
+            ui.label(RichText::new(format!("Top {} values", TOP_VALUES_COUNT)).strong());
+            Grid::new("column_profile_top_values").num_columns(2).striped(true).show(ui, |ui| {
+                for (value, count) in profile.top_values.iter() {
+                    ui.add(egui::Label::new(value).truncate(true));
+                    ui.label(count.to_string());
+                    ui.end_row();
+                }
+            });
+        }
+    }
+
+    fn histogram_ui(ui: &mut Ui, histogram: &[usize]) {
+        let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), 80.0), Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_stroke(rect, 0.0, Stroke::new(1.0, ui.visuals().widgets.noninteractive.bg_stroke.color));
+        let max_count = histogram.iter().copied().max().unwrap_or(0).max(1);
+        let bar_width = rect.width() / histogram.len() as f32;
+        let mut hovered_bin = None;
+        for (i, count) in histogram.iter().enumerate() {
+            let height = rect.height() * (*count as f32 / max_count as f32);
+            let bar = egui::Rect::from_min_max(
+                egui::pos2(rect.left() + i as f32 * bar_width + 1.0, rect.bottom() - height),
+                egui::pos2(rect.left() + (i + 1) as f32 * bar_width - 1.0, rect.bottom()),
+            );
+            let hovered = response.hover_pos().is_some_and(|pos| pos.x >= bar.left() && pos.x <= bar.right());
+            if hovered {
+                hovered_bin = Some(*count);
+            }
+            painter.rect_filled(bar, 0.0, if hovered { ACTIVE_COLOR } else { Color32::from_rgb(100, 149, 237) });
+        }
+        if let Some(count) = hovered_bin {
+            response.on_hover_text(format!("{} values", count));
+        }
+    }
+}
+
+fn percentage(count: usize, total: usize) -> String {
+    if total == 0 {
+        return count.to_string();
+    }
+    format!("{} ({:.1}%)", count, count as f64 * 100.0 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(value: &str) -> Option<(ValueType, Option<String>)> {
+        Some((ValueType::Number, Some(value.to_string())))
+    }
+
+    fn string(value: &str) -> Option<(ValueType, Option<String>)> {
+        Some((ValueType::String, Some(value.to_string())))
+    }
+
+    #[test]
+    fn median_of_odd_and_even_count() {
+        let stats = ColumnProfile::number_stats(vec![3.0, 1.0, 2.0]).unwrap();
+        assert_eq!(stats.median, 2.0);
+        let stats = ColumnProfile::number_stats(vec![4.0, 1.0, 3.0, 2.0]).unwrap();
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 4.0);
+        assert_eq!(stats.mean, 2.5);
+        assert!(ColumnProfile::number_stats(vec![]).is_none());
+    }
+
+    #[test]
+    fn histogram_bins() {
+        let stats = ColumnProfile::number_stats(vec![0.0, 5.0, 10.0]).unwrap();
+        assert_eq!(stats.histogram.len(), HISTOGRAM_BINS);
+        assert_eq!(stats.histogram[0], 1);
+        assert_eq!(stats.histogram[HISTOGRAM_BINS / 2], 1);
+        // Max falls in last bin
+        assert_eq!(stats.histogram[HISTOGRAM_BINS - 1], 1);
+    }
+
+    #[test]
+    fn histogram_of_equal_numbers() {
+        let stats = ColumnProfile::number_stats(vec![7.0, 7.0, 7.0]).unwrap();
+        assert_eq!(stats.histogram[0], 3);
+        assert_eq!(stats.histogram.iter().sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn null_missing_and_present() {
+        let profile = ColumnProfile::compute(vec![
+            number("1"),
+            None,
+            Some((ValueType::Null, None)),
+            Some((ValueType::Null, Some("null".to_string()))),
+            number("3"),
+        ]);
+        assert_eq!(profile.rows_count, 5);
+        assert_eq!(profile.missing_count, 1);
+        assert_eq!(profile.null_count, 2);
+        assert_eq!(profile.distinct_count, 2);
+        assert_eq!(profile.types, vec![("number", 2), ("null", 2)]);
+        assert_eq!(profile.numbers.unwrap().mean, 2.0);
+    }
+
+    #[test]
+    fn top_values_ties_ordered_by_value() {
+        let profile = ColumnProfile::compute(vec![string("b"), string("a"), string("c"), string("c"), string("b"), string("a"), string("d")]);
+        assert_eq!(profile.top_values, vec![("a".to_string(), 2), ("b".to_string(), 2), ("c".to_string(), 2), ("d".to_string(), 1)]);
+        assert!(profile.numbers.is_none());
+    }
+
+    #[test]
+    fn top_values_truncated() {
+        let values = (0..TOP_VALUES_COUNT + 5).map(|i| number(&i.to_string())).collect();
+        let profile = ColumnProfile::compute(values);
+        assert_eq!(profile.top_values.len(), TOP_VALUES_COUNT);
+        assert_eq!(profile.distinct_count, TOP_VALUES_COUNT + 5);
+    }
+
+    #[test]
+    fn shortest_and_longest_strings_by_chars() {
+        // "ééé" has 3 chars but 6 bytes
+        let profile = ColumnProfile::compute(vec![string("abcd"), string("ééé"), number("123456")]);
+        assert_eq!(profile.shortest_string.as_deref(), Some("ééé"));
+        assert_eq!(profile.longest_string.as_deref(), Some("abcd"));
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+/// Column widths resized by user are stored in user config directory, by file path then by column name.
+fn config_file() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("json-editor").join("column_widths.json"))
+}
+
+fn file_key(file: &Path) -> String {
+    fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf()).display().to_string()
+}
+
+fn read_all(config_file: &Path) -> Map<String, Value> {
+    fs::read_to_string(config_file).ok()
+        .and_then(|content| serde_json::from_str::<Map<String, Value>>(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn load(file: &Path) -> HashMap<String, f32> {
+    let Some(config_file) = config_file() else {
+        return HashMap::new();
+    };
+    match read_all(&config_file).get(&file_key(file)) {
+        Some(Value::Object(widths)) => widths.iter()
+            .filter_map(|(column, width)| width.as_f64().map(|width| (column.clone(), width as f32)))
+            .collect(),
+        _ => HashMap::new()
+    }
+}
+
+pub fn save(file: &Path, widths: &HashMap<String, f32>) -> Result<(), String> {
+    let config_file = config_file().ok_or_else(|| "No config directory found".to_string())?;
+    let mut all_widths = read_all(&config_file);
+    let widths = widths.iter()
+        .map(|(column, width)| (column.clone(), Value::from(width.round() as f64)))
+        .collect::<Map<String, Value>>();
+    all_widths.insert(file_key(file), Value::Object(widths));
+    if let Some(config_dir) = config_file.parent() {
+        fs::create_dir_all(config_dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(&Value::Object(all_widths)).map_err(|e| e.to_string())?;
+    fs::write(&config_file, content).map_err(|e| e.to_string())
+}
@@ -3,11 +3,11 @@ use std::time::{Duration,Instant};
 macro_rules! log {
     () => {
         #[cfg(not(target_arch = "wasm32"))]
-        print!("\n")
+        eprint!("\n")
     };
     ($($arg:tt)*) => {{
         #[cfg(not(target_arch = "wasm32"))]
-        println!($($arg)*);
+        eprintln!($($arg)*);
         #[cfg(target_arch = "wasm32")]
         web_sys::console::log_1(&std::format_args!($($arg)*).as_str().into());
     }};
@@ -22,6 +22,15 @@ pub fn now() -> crate::compatibility::InstantWrapper {
     InstantWrapper::now()
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub fn unix_time_secs() -> u64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+#[cfg(target_arch = "wasm32")]
+pub fn unix_time_secs() -> u64 {
+    (web_sys::js_sys::Date::now() / 1000.0) as u64
+}
+
 #[cfg(target_arch = "wasm32")]
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Hash)]
 pub struct InstantWrapper(Duration);
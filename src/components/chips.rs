@@ -0,0 +1,35 @@
+use egui::{Frame, Margin, Response, RichText, Rounding, Sense, Ui};
+
+use crate::parser::{primitive_array_items, primitive_text};
+
+/// Arrays having more elements, or a longer json text, are displayed as json text
+const CHIPS_MAX_ITEMS: usize = 10;
+pub const CHIPS_MAX_JSON_LEN: usize = 512;
+
+/// Elements of a serialized array small enough to be displayed as chips.
+pub fn inline_items(value: &str) -> Option<Vec<serde_json::Value>> {
+    if value.len() > CHIPS_MAX_JSON_LEN {
+        return None;
+    }
+    primitive_array_items(value).filter(|items| items.len() <= CHIPS_MAX_ITEMS)
+}
+
+/// Display array elements as small rounded labels, in a single line.
+pub fn chips(ui: &mut Ui, items: &[serde_json::Value]) -> Response {
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 3.0;
+        if items.is_empty() {
+            ui.label(RichText::new("[]").weak());
+        }
+        for item in items {
+            Frame::none()
+                .fill(ui.visuals().widgets.inactive.weak_bg_fill)
+                .rounding(Rounding::same(6.0))
+                .inner_margin(Margin::symmetric(5.0, 0.0))
+                .show(ui, |ui| {
+                    let text = RichText::new(primitive_text(item));
+                    ui.label(if item.is_string() { text } else { text.monospace() });
+                });
+        }
+    }).response.interact(Sense::click())
+}
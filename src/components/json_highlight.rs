@@ -0,0 +1,72 @@
+use egui::{Color32, Style, TextFormat, TextStyle};
+use egui::text::LayoutJob;
+
+struct JsonTheme {
+    key: Color32,
+    string: Color32,
+    number: Color32,
+    keyword: Color32,
+    punctuation: Color32,
+}
+
+impl JsonTheme {
+    fn new(style: &Style) -> Self {
+        if style.visuals.dark_mode {
+            Self {
+                key: Color32::from_rgb(156, 220, 254),
+                string: Color32::from_rgb(206, 145, 120),
+                number: Color32::from_rgb(181, 206, 168),
+                keyword: Color32::from_rgb(86, 156, 214),
+                punctuation: style.visuals.text_color(),
+            }
+        } else {
+            Self {
+                key: Color32::from_rgb(4, 81, 165),
+                string: Color32::from_rgb(163, 21, 21),
+                number: Color32::from_rgb(9, 134, 88),
+                keyword: Color32::from_rgb(0, 0, 255),
+                punctuation: style.visuals.text_color(),
+            }
+        }
+    }
+}
+
+/// Layout json text in monospace with keys, strings, numbers and literals colored, text does not need to be valid json.
+pub fn highlight_json(style: &Style, text: &str) -> LayoutJob {
+    let theme = JsonTheme::new(style);
+    let font_id = TextStyle::Monospace.resolve(style);
+    let mut job = LayoutJob::default();
+    let bytes = text.as_bytes();
+    let mut start = 0;
+    while start < bytes.len() {
+        let (end, color) = match bytes[start] {
+            b'"' => {
+                let mut end = start + 1;
+                while end < bytes.len() && bytes[end] != b'"' {
+                    end += if bytes[end] == b'\\' { 2 } else { 1 };
+                }
+                let mut end = (end + 1).min(bytes.len());
+                while !text.is_char_boundary(end) {
+                    end += 1;
+                }
+                let is_key = text[end..].trim_start().starts_with(':');
+                (end, if is_key { theme.key } else { theme.string })
+            }
+            b'-' | b'0'..=b'9' => {
+                let length = text[start + 1..].find(|c: char| !matches!(c, '0'..='9' | '.' | 'e' | 'E' | '+' | '-')).unwrap_or(text.len() - start - 1);
+                (start + 1 + length, theme.number)
+            }
+            b'a'..=b'z' => {
+                let length = text[start..].find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(text.len() - start);
+                (start + length, theme.keyword)
+            }
+            _ => {
+                let length = text[start..].find(|c: char| matches!(c, '"' | '-' | '0'..='9' | 'a'..='z')).unwrap_or(text.len() - start);
+                (start + length, theme.punctuation)
+            }
+        };
+        job.append(&text[start..end], 0.0, TextFormat::simple(font_id.clone(), color));
+        start = end;
+    }
+    job
+}
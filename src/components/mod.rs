@@ -2,4 +2,7 @@ pub mod table;
 pub mod fps;
 pub mod popover;
 pub mod icon;
-pub(crate) mod cache;
\ No newline at end of file
+pub(crate) mod cache;
+pub mod value_editor;
+pub mod chips;
+pub mod json_highlight;
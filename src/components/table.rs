@@ -149,6 +149,7 @@ impl From<Vec<Size>> for Sizing {
 
 use egui::{scroll_area::ScrollBarVisibility, Align, NumExt as _, Rangef, Rect, Response, ScrollArea, Ui, Vec2, Vec2b, Pos2, Sense, Id, Color32, Stroke};
 use egui::scroll_area::ScrollAreaOutput;
+use std::ops::RangeInclusive;
 
 #[derive(Clone, Copy)]
 pub(crate) enum CellSize {
@@ -447,6 +448,9 @@ pub struct Column {
     clip: bool,
 
     resizable: Option<bool>,
+
+    /// Width overriding the one kept in table state
+    width: Option<f32>,
 }
 
 impl Column {
@@ -470,9 +474,19 @@ impl Column {
             width_range: Rangef::new(0.0, f32::INFINITY),
             resizable: None,
             clip: false,
+            width: None,
         }
     }
 
+    /// Force column width, e.g. a width restored from user preferences.
+    ///
+    /// Unlike [`Self::initial`], it is applied on every frame, ignoring the width stored in table state.
+    #[inline]
+    pub fn width(mut self, width: Option<f32>) -> Self {
+        self.width = width;
+        self
+    }
+
     /// Can this column be resized by dragging the column separator?
     ///
     /// If you don't call this, the fallback value of
@@ -594,7 +608,8 @@ pub struct TableBuilder<'a> {
     cell_layout: egui::Layout,
     scroll_options: TableScrollOptions,
     sense: egui::Sense,
-    is_pinned_column_table: bool
+    is_pinned_column_table: bool,
+    selected_cells: Option<(RangeInclusive<usize>, RangeInclusive<usize>)>,
 }
 
 impl<'a> TableBuilder<'a> {
@@ -609,6 +624,7 @@ impl<'a> TableBuilder<'a> {
             scroll_options: Default::default(),
             sense: egui::Sense::hover(),
             is_pinned_column_table: false,
+            selected_cells: None,
         }
     }
 
@@ -640,6 +656,13 @@ impl<'a> TableBuilder<'a> {
     }
 
 
+    /// Paint cells within these (columns, rows) index ranges as selected.
+    #[inline]
+    pub fn selected_cells(mut self, selected_cells: Option<(RangeInclusive<usize>, RangeInclusive<usize>)>) -> Self {
+        self.selected_cells = selected_cells;
+        self
+    }
+
     /// Set a row to scroll to.
     ///
     /// `align` specifies if the row should be positioned in the top, center, or bottom of the view
@@ -730,7 +753,8 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
-            is_pinned_column_table
+            is_pinned_column_table,
+            selected_cells,
         } = self;
 
         let striped = striped.unwrap_or(ui.visuals().striped);
@@ -740,7 +764,12 @@ impl<'a> TableBuilder<'a> {
         let initial_widths =
             to_sizing(&columns).to_lengths(available_width, ui.spacing().item_spacing.x);
         let mut max_used_widths = vec![0.0; initial_widths.len()];
-        let (had_state, state) = TableState::load(ui, initial_widths, state_id);
+        let (had_state, mut state) = TableState::load(ui, initial_widths, state_id);
+        for (column_width, column) in state.column_widths.iter_mut().zip(columns.iter()) {
+            if let Some(width) = column.width {
+                *column_width = column.width_range.clamp(width);
+            }
+        }
         let is_first_frame = !had_state;
         let first_frame_auto_size_columns = is_first_frame && columns.iter().any(|c| c.is_auto());
 
@@ -796,6 +825,7 @@ impl<'a> TableBuilder<'a> {
                 highlighted: false,
                 highlighted_cell: None,
                 selected_cell: None,
+                selected_cells: &None,
                 hovered_cell_index_id: None,
                 is_pinned_column_table,
             });
@@ -817,6 +847,7 @@ impl<'a> TableBuilder<'a> {
             scroll_options,
             sense,
             is_pinned_column_table,
+            selected_cells,
         }
     }
 }
@@ -878,13 +909,14 @@ pub struct Table<'a> {
     scroll_options: TableScrollOptions,
 
     sense: egui::Sense,
-    is_pinned_column_table: bool
+    is_pinned_column_table: bool,
+    selected_cells: Option<(RangeInclusive<usize>, RangeInclusive<usize>)>,
 }
 
 impl<'a> Table<'a> {
 
     /// Create table body after adding a header row
-    pub fn body<F>(self, stored_hovered_row_index: Option<usize>, search_matching_row_index: Option<usize>, focused_cell: Option<(usize, usize, bool)>, add_body_contents: F) -> ScrollAreaOutput<Vec<f32>>
+    pub fn body<F>(self, stored_hovered_row_index: Option<usize>, search_matching_row_index: Option<usize>, focused_cell: Option<(usize, usize, bool)>, add_body_contents: F) -> ScrollAreaOutput<TableOutput>
         where
             F: for<'b> FnOnce(TableBody<'b>),
     {
@@ -902,6 +934,7 @@ impl<'a> Table<'a> {
             cell_layout,
             scroll_options,
             sense, is_pinned_column_table,
+            selected_cells,
         } = self;
 
         let TableScrollOptions {
@@ -932,6 +965,7 @@ impl<'a> Table<'a> {
         }
 
         let columns_ref = &columns;
+        let selected_cells_ref = &selected_cells;
         let widths_ref = &state.column_widths;
         let max_used_widths_ref = &mut max_used_widths;
 
@@ -999,6 +1033,7 @@ impl<'a> Table<'a> {
                     search_matching_row_index,
                     hovered_cell_index_id,
                     is_pinned_column_table,
+                    selected_cells: selected_cells_ref,
                 });
 
                 if scroll_to_row.is_some() && scroll_to_y_range.is_none() {
@@ -1018,6 +1053,9 @@ impl<'a> Table<'a> {
 
         let bottom = ui.min_rect().bottom();
 
+        let mut resizing_column = None;
+        let mut resized_column = None;
+        let mut auto_fit_column = None;
         let spacing_x = ui.spacing().item_spacing.x;
         let mut x = cursor_position.x - spacing_x * 0.5;
         for (i, column_width) in state.column_widths.iter_mut().enumerate() {
@@ -1064,7 +1102,9 @@ impl<'a> Table<'a> {
                 if resize_response.double_clicked() {
                     // Resize to the minimum of what is needed.
                     *column_width = width_range.clamp(max_used_widths[i]);
+                    auto_fit_column = Some(i);
                 } else if resize_response.dragged() {
+                    resizing_column = Some(i);
                     if let Some(pointer) = ui.ctx().pointer_latest_pos() {
                         let mut new_width = *column_width + pointer.x - x;
                         if !column.clip {
@@ -1085,6 +1125,8 @@ impl<'a> Table<'a> {
 
                         *column_width = new_width;
                     }
+                } else if resize_response.drag_stopped() {
+                    resized_column = Some(i);
                 }
 
                 let dragging_something_else =
@@ -1113,11 +1155,36 @@ impl<'a> Table<'a> {
 
             available_width -= *column_width + spacing_x;
         }
+        let column_widths = state.column_widths.clone();
         state.store(ui, state_id);
-        scroll_area_output
+        ScrollAreaOutput {
+            inner: TableOutput {
+                columns_offset: scroll_area_output.inner,
+                column_widths,
+                resizing_column,
+                resized_column,
+                auto_fit_column,
+            },
+            id: scroll_area_output.id,
+            state: scroll_area_output.state,
+            content_size: scroll_area_output.content_size,
+            inner_rect: scroll_area_output.inner_rect,
+        }
     }
 }
 
+/// Columns layout after table has been drawn, along with column separator interactions.
+pub struct TableOutput {
+    pub columns_offset: Vec<f32>,
+    pub column_widths: Vec<f32>,
+    /// Column whose separator is being dragged
+    pub resizing_column: Option<usize>,
+    /// Column whose separator has just been released
+    pub resized_column: Option<usize>,
+    /// Column whose separator has been double clicked
+    pub auto_fit_column: Option<usize>,
+}
+
 /// The body of a table.
 ///
 /// Is created by calling `body` on a [`Table`] (after adding a header row) or [`TableBuilder`] (without a header row).
@@ -1156,7 +1223,8 @@ pub struct TableBody<'a> {
     pub scroll_offset_x: f32,
     pub first_col_visible_width: f32,
     pub search_matching_row_index: Option<usize>,
-    is_pinned_column_table: bool
+    is_pinned_column_table: bool,
+    selected_cells: &'a Option<(RangeInclusive<usize>, RangeInclusive<usize>)>,
 }
 
 impl<'a> TableBody<'a> {
@@ -1219,6 +1287,7 @@ impl<'a> TableBody<'a> {
                 highlighted: self.search_matching_row_index == Some(row_index),
                 highlighted_cell: None,
                 selected_cell: self.hovered_cell_index,
+                selected_cells: self.selected_cells,
                 selected: false,
                 response: &mut response,
                 remainder_with: 0.0,
@@ -1285,6 +1354,7 @@ pub struct TableRow<'a, 'b> {
     pub highlighted: bool,
     pub highlighted_cell: Option<usize>,
     pub selected_cell: Option<(usize, usize, bool)>,
+    selected_cells: &'b Option<(RangeInclusive<usize>, RangeInclusive<usize>)>,
     hovered_cell_index_id: Option<egui::Id>,
     is_pinned_column_table: bool
 }
@@ -1398,11 +1468,13 @@ impl<'a, 'b> TableRow<'a, 'b> {
             } else {
                 false
             };
+            let selected = self.selected || self.selected_cells.as_ref()
+                .is_some_and(|(columns, rows)| columns.contains(col_index) && rows.contains(&self.row_index));
             let flags = StripLayoutFlags {
                 clip,
                 striped: self.striped,
                 hovered: self.hovered,
-                selected: self.selected,
+                selected,
                 selected_cell,
                 highlighted: self.highlighted,
             };
@@ -0,0 +1,321 @@
+use std::mem;
+
+use egui::{Area, Button, DragValue, Frame, Grid, Id, Key, Order, RichText, ScrollArea, TextEdit, Ui};
+
+use crate::parser::{primitive_array_items, primitive_text};
+use crate::schema::ValueEditor;
+
+const MONTHS: [&str; 12] = ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+const WEEK_DAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+pub enum EditOutcome {
+    Editing,
+    Commit,
+    Cancel,
+}
+
+#[derive(Clone)]
+struct DateTimeState {
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    // Fraction of second and timezone, kept as is
+    suffix: String,
+    shown_year: i32,
+    shown_month: u32,
+}
+
+impl DateTimeState {
+    /// Parse `YYYY-MM-DD` optionally followed by `THH:MM:SS`, today when value is not a valid date.
+    fn parse(value: &str) -> Self {
+        let part = |start: usize, end: usize| value.get(start..end).and_then(|part| part.parse::<u32>().ok());
+        let (year, month, day) = match (part(0, 4), part(5, 7), part(8, 10)) {
+            (Some(year), Some(month @ 1..=12), Some(day)) if (1..=days_in_month(year as i32, month)).contains(&day) => (year as i32, month, day),
+            _ => today(),
+        };
+        let has_time = matches!(value.get(10..11), Some("T" | " "));
+        let (hour, minute, second) = if has_time {
+            (part(11, 13).filter(|hour| *hour < 24).unwrap_or(0), part(14, 16).filter(|minute| *minute < 60).unwrap_or(0),
+             part(17, 19).filter(|second| *second < 60).unwrap_or(0))
+        } else {
+            (0, 0, 0)
+        };
+        let suffix = value.get(19..).filter(|_| has_time).unwrap_or("Z").to_string();
+        Self { year, month, day, hour, minute, second, suffix, shown_year: year, shown_month: month }
+    }
+
+    fn format(&self, with_time: bool) -> String {
+        if with_time {
+            format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}", self.year, self.month, self.day, self.hour, self.minute, self.second, self.suffix)
+        } else {
+            format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ArrayItem {
+    text: String,
+    is_string: bool,
+}
+
+#[derive(Clone)]
+struct ArrayEditorState {
+    items: Vec<ArrayItem>,
+    new_item: String,
+}
+
+impl ArrayEditorState {
+    fn parse(value: &str) -> Self {
+        let items = primitive_array_items(value).unwrap_or_default().iter()
+            .map(|item| ArrayItem { text: primitive_text(item), is_string: item.is_string() })
+            .collect();
+        Self { items, new_item: String::new() }
+    }
+
+    /// Items keep their type unless their text is no longer valid for it, then they become strings.
+    fn format(&self) -> String {
+        let items = self.items.iter().map(|item| {
+            if item.is_string {
+                serde_json::Value::String(item.text.clone())
+            } else {
+                serde_json::from_str::<serde_json::Value>(&item.text).ok()
+                    .filter(|value| !value.is_array() && !value.is_object() && !value.is_string())
+                    .unwrap_or_else(|| serde_json::Value::String(item.text.clone()))
+            }
+        }).collect::<Vec<serde_json::Value>>();
+        serde_json::Value::Array(items).to_string()
+    }
+}
+
+/// Editor displayed in a popup below edited cell, to pick a value from schema enum or a date from a calendar.
+pub fn popup_editor(ui: &mut Ui, id: Id, editor: &ValueEditor, value: &mut String) -> EditOutcome {
+    let outcome = popup(ui, id, value, |ui, value, outcome| {
+        match editor {
+            ValueEditor::Enum(values) => enum_ui(ui, values, value, outcome),
+            ValueEditor::Date => date_time_ui(ui, id, value, false, outcome),
+            ValueEditor::DateTime => date_time_ui(ui, id, value, true, outcome),
+        }
+    });
+    if !matches!(outcome, EditOutcome::Editing) {
+        ui.data_mut(|data| data.remove::<DateTimeState>(id));
+    }
+    outcome
+}
+
+/// Editor displayed in a popup below edited cell, to add, remove and reorder elements of an array of primitives.
+pub fn array_popup_editor(ui: &mut Ui, id: Id, value: &mut String) -> EditOutcome {
+    let outcome = popup(ui, id, value, |ui, value, outcome| array_ui(ui, id, value, outcome));
+    if !matches!(outcome, EditOutcome::Editing) {
+        ui.data_mut(|data| data.remove::<ArrayEditorState>(id));
+    }
+    outcome
+}
+
+fn popup(ui: &mut Ui, id: Id, value: &mut String, add_contents: impl FnOnce(&mut Ui, &mut String, &mut EditOutcome)) -> EditOutcome {
+    ui.label(value.as_str());
+    let cell_rect = ui.max_rect();
+    let mut outcome = EditOutcome::Editing;
+    let area_response = Area::new(id)
+        .order(Order::Foreground)
+        .fixed_pos(cell_rect.left_bottom())
+        .constrain(true)
+        .show(ui.ctx(), |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| add_contents(ui, value, &mut outcome));
+        }).response;
+    let clicked_outside = ui.input(|i| i.pointer.any_pressed()
+        && i.pointer.interact_pos().is_some_and(|pos| !area_response.rect.contains(pos) && !cell_rect.contains(pos)));
+    if clicked_outside {
+        outcome = EditOutcome::Cancel;
+    }
+    outcome
+}
+
+fn array_ui(ui: &mut Ui, id: Id, value: &mut String, outcome: &mut EditOutcome) {
+    let mut state = ui.data(|data| data.get_temp::<ArrayEditorState>(id)).unwrap_or_else(|| ArrayEditorState::parse(value));
+    let mut moved_up: Option<usize> = None;
+    let mut removed: Option<usize> = None;
+    ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+        let items_count = state.items.len();
+        for (i, item) in state.items.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.add_enabled(i > 0, Button::new("⬆").small()).on_hover_text("Move up").clicked() {
+                    moved_up = Some(i);
+                }
+                if ui.add_enabled(i + 1 < items_count, Button::new("⬇").small()).on_hover_text("Move down").clicked() {
+                    moved_up = Some(i + 1);
+                }
+                ui.add(TextEdit::singleline(&mut item.text).desired_width(160.0));
+                if ui.small_button("🗙").on_hover_text("Remove").clicked() {
+                    removed = Some(i);
+                }
+            });
+        }
+    });
+    if let Some(i) = moved_up {
+        state.items.swap(i - 1, i);
+    }
+    if let Some(i) = removed {
+        state.items.remove(i);
+    }
+    ui.horizontal(|ui| {
+        let response = ui.add(TextEdit::singleline(&mut state.new_item).hint_text("New element").desired_width(160.0));
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+        if (ui.button("Add").clicked() || submitted) && !state.new_item.is_empty() {
+            // New element has the same type as the first one, or is a string
+            let is_string = state.items.first().is_none_or(|item| item.is_string);
+            state.items.push(ArrayItem { text: mem::take(&mut state.new_item), is_string });
+            response.request_focus();
+        }
+    });
+    ui.separator();
+    ui.horizontal(|ui| {
+        if ui.button("OK").clicked() {
+            *value = state.format();
+            *outcome = EditOutcome::Commit;
+        }
+        if ui.button("Cancel").clicked() {
+            *outcome = EditOutcome::Cancel;
+        }
+    });
+    ui.data_mut(|data| data.insert_temp(id, state));
+}
+
+fn enum_ui(ui: &mut Ui, values: &[String], value: &mut String, outcome: &mut EditOutcome) {
+    ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+        for enum_value in values.iter() {
+            if ui.selectable_label(enum_value.eq(value), enum_value.as_str()).clicked() {
+                *value = enum_value.clone();
+                *outcome = EditOutcome::Commit;
+            }
+        }
+    });
+}
+
+fn date_time_ui(ui: &mut Ui, id: Id, value: &mut String, with_time: bool, outcome: &mut EditOutcome) {
+    let mut state = ui.data(|data| data.get_temp::<DateTimeState>(id)).unwrap_or_else(|| DateTimeState::parse(value));
+    ui.horizontal(|ui| {
+        if ui.small_button("◀").clicked() {
+            if state.shown_month == 1 {
+                state.shown_month = 12;
+                state.shown_year -= 1;
+            } else {
+                state.shown_month -= 1;
+            }
+        }
+        ui.label(RichText::new(format!("{} {}", MONTHS[state.shown_month as usize - 1], state.shown_year)).strong());
+        if ui.small_button("▶").clicked() {
+            if state.shown_month == 12 {
+                state.shown_month = 1;
+                state.shown_year += 1;
+            } else {
+                state.shown_month += 1;
+            }
+        }
+    });
+    Grid::new(id.with("calendar")).num_columns(7).spacing([2.0, 2.0]).show(ui, |ui| {
+        for week_day in WEEK_DAYS {
+            ui.label(RichText::new(week_day).weak());
+        }
+        ui.end_row();
+        let first_week_day = week_day(state.shown_year, state.shown_month, 1);
+        for _ in 0..first_week_day {
+            ui.label("");
+        }
+        for day in 1..=days_in_month(state.shown_year, state.shown_month) {
+            let selected = state.year == state.shown_year && state.month == state.shown_month && state.day == day;
+            if ui.selectable_label(selected, format!("{:>2}", day)).clicked() {
+                state.year = state.shown_year;
+                state.month = state.shown_month;
+                state.day = day;
+            }
+            if (first_week_day + day).is_multiple_of(7) {
+                ui.end_row();
+            }
+        }
+    });
+    if with_time {
+        ui.horizontal(|ui| {
+            ui.label("Time");
+            ui.add(DragValue::new(&mut state.hour).clamp_range(0..=23).custom_formatter(|n, _| format!("{:02}", n)));
+            ui.label(":");
+            ui.add(DragValue::new(&mut state.minute).clamp_range(0..=59).custom_formatter(|n, _| format!("{:02}", n)));
+            ui.label(":");
+            ui.add(DragValue::new(&mut state.second).clamp_range(0..=59).custom_formatter(|n, _| format!("{:02}", n)));
+        });
+    }
+    ui.horizontal(|ui| {
+        if ui.button("OK").clicked() {
+            *value = state.format(with_time);
+            *outcome = EditOutcome::Commit;
+        }
+        if ui.button("Cancel").clicked() {
+            *outcome = EditOutcome::Cancel;
+        }
+    });
+    ui.data_mut(|data| data.insert_temp(id, state));
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Day of week, 0 being monday.
+fn week_day(year: i32, month: u32, day: u32) -> u32 {
+    const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 { year - 1 } else { year };
+    let sunday_based = (year + year / 4 - year / 100 + year / 400 + OFFSETS[month as usize - 1] + day as i32).rem_euclid(7);
+    ((sunday_based + 6) % 7) as u32
+}
+
+fn today() -> (i32, u32, u32) {
+    // Civil date from days since unix epoch
+    let days = (crate::compatibility::unix_time_secs() / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> (i32, u32, u32) {
+        let state = DateTimeState::parse(value);
+        (state.year, state.month, state.day)
+    }
+
+    #[test]
+    fn parse_checks_day_of_month() {
+        assert_eq!(date("2024-02-29"), (2024, 2, 29));
+        assert_eq!(date("2023-02-29"), today());
+        assert_eq!(date("2023-04-31"), today());
+        assert_eq!(date("2023-13-01"), today());
+        assert_eq!(date("2023-12-31T10:00:00Z"), (2023, 12, 31));
+    }
+
+    #[test]
+    fn parse_ignores_invalid_time() {
+        let state = DateTimeState::parse("2023-01-02T25:61:30.5+02:00");
+        assert_eq!((state.hour, state.minute, state.second), (0, 0, 30));
+        assert_eq!(state.format(true), "2023-01-02T00:00:30.5+02:00");
+    }
+}
@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use json_flat_parser::{JsonArrayEntries, ValueType};
+use rust_xlsxwriter::{Color, Format, FormatBorder, Workbook, Worksheet, XlsxError};
+
+use crate::array_table::{ArrayTable, Column};
+
+// Excel sheet hard limits
+const XLSX_MAX_ROWS: usize = 1_048_576;
+const XLSX_MAX_COLUMNS: usize = 16_384;
+
+/// Write rows at `filtered_nodes` into an xlsx workbook, pinned columns first then selected columns.
+/// Pinned columns are frozen, numbers and booleans keep their type, nested values are written as json text.
+pub fn save_to_xlsx(parent_pointer: &str, nodes: &[JsonArrayEntries<String>], filtered_nodes: &[usize],
+                    pinned_columns: &[Column], selected_columns: &[Column], file_path: &Path) -> Result<(), String> {
+    let is_data_column = |c: &&Column| !c.name.is_empty() && !c.name.eq("/#");
+    // Row number column is displayed only, it is not part of the document
+    let pinned_columns_count = pinned_columns.iter().filter(is_data_column).count();
+    let columns = pinned_columns.iter().chain(selected_columns.iter()).filter(is_data_column).collect::<Vec<&Column>>();
+    if columns.len() > XLSX_MAX_COLUMNS {
+        return Err(format!("Cannot export {} columns, xlsx sheet is limited to {} columns", columns.len(), XLSX_MAX_COLUMNS));
+    }
+    if filtered_nodes.len() + 1 > XLSX_MAX_ROWS {
+        return Err(format!("Cannot export {} rows, xlsx sheet is limited to {} rows", filtered_nodes.len(), XLSX_MAX_ROWS - 1));
+    }
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    write_sheet(worksheet, parent_pointer, nodes, filtered_nodes, &columns, pinned_columns_count).map_err(|e| e.to_string())?;
+    workbook.save(file_path).map_err(|e| e.to_string())
+}
+
+fn write_sheet(worksheet: &mut Worksheet, parent_pointer: &str, nodes: &[JsonArrayEntries<String>], filtered_nodes: &[usize],
+               columns: &[&Column], pinned_columns_count: usize) -> Result<(), XlsxError> {
+    let header_format = Format::new().set_bold()
+        .set_background_color(Color::RGB(0xDDEBF7))
+        .set_border_bottom(FormatBorder::Thin);
+    let parent_pointer = parent_pointer.to_string();
+    for (col, column) in columns.iter().enumerate() {
+        let col = col as u16;
+        worksheet.write_string_with_format(0, col, column.name.as_str(), &header_format)?;
+        worksheet.set_column_width(col, (column.name.len() + 3).clamp(10, 60) as f64)?;
+    }
+    for (row, row_index) in filtered_nodes.iter().enumerate() {
+        let row_data = &nodes[*row_index];
+        let row = (row + 1) as u32;
+        for (col, column) in columns.iter().enumerate() {
+            let col = col as u16;
+            let Some(entry) = ArrayTable::get_pointer_for_column(&parent_pointer, &&row_data.entries, row_data.index, column) else {
+                continue;
+            };
+            let Some(value) = entry.value.as_ref() else {
+                continue;
+            };
+            match entry.pointer.value_type {
+                ValueType::Number => {
+                    if let Ok(number) = value.parse::<f64>() {
+                        worksheet.write_number(row, col, number)?;
+                    } else {
+                        worksheet.write_string(row, col, value)?;
+                    }
+                }
+                ValueType::Bool => {
+                    worksheet.write_boolean(row, col, value.eq("true"))?;
+                }
+                ValueType::Null => {}
+                ValueType::Array(_) | ValueType::Object(_) => {
+                    worksheet.write_string(row, col, value.replace('\n', ""))?;
+                }
+                _ => {
+                    worksheet.write_string(row, col, value)?;
+                }
+            }
+        }
+    }
+    worksheet.set_freeze_panes(1, pinned_columns_count.min(columns.len()) as u16)?;
+    Ok(())
+}
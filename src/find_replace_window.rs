@@ -0,0 +1,198 @@
+use egui::{Color32, ComboBox, Context, RichText, TextEdit};
+use egui_extras::{Column as TableColumn, TableBuilder};
+use json_flat_parser::{JsonArrayEntries, PointerKey, ValueType};
+use regex::{NoExpand, Regex};
+
+use crate::array_table::{ArrayTable, Column};
+use crate::parser::{search_regex, SearchOptions};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ReplaceScope {
+    SelectedColumn,
+    VisibleColumns,
+}
+
+impl ReplaceScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SelectedColumn => "selected column",
+            Self::VisibleColumns => "visible columns",
+        }
+    }
+}
+
+pub struct Replacement {
+    pub row_index: usize,
+    pub pointer: PointerKey,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+pub enum FindReplaceAction {
+    None,
+    Preview,
+    Apply,
+}
+
+pub struct FindReplaceWindow {
+    pub find: String,
+    pub replace: String,
+    pub regex: bool,
+    pub case_sensitive: bool,
+    pub scope: ReplaceScope,
+    pub column: Option<String>,
+    pub filtered_rows_only: bool,
+    pub replacements: Vec<Replacement>,
+    pub error: Option<String>,
+}
+
+impl FindReplaceWindow {
+    pub fn new(column: Option<String>) -> Self {
+        Self {
+            find: String::new(),
+            replace: String::new(),
+            regex: false,
+            case_sensitive: true,
+            scope: if column.is_some() { ReplaceScope::SelectedColumn } else { ReplaceScope::VisibleColumns },
+            column,
+            filtered_rows_only: true,
+            replacements: vec![],
+            error: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool, columns: &[&Column]) -> FindReplaceAction {
+        let mut action = FindReplaceAction::None;
+        egui::Window::new("Find and replace")
+            .open(open)
+            .default_width(520.0)
+            .resizable([true, true])
+            .show(ctx, |ui| {
+                let mut changed = false;
+                egui::Grid::new("find_replace_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Find");
+                    changed |= ui.add(TextEdit::singleline(&mut self.find).desired_width(300.0)).changed();
+                    ui.end_row();
+                    ui.label("Replace with");
+                    changed |= ui.add(TextEdit::singleline(&mut self.replace).desired_width(300.0)
+                        .hint_text(if self.regex { "$1 to reference capture group" } else { "" })).changed();
+                    ui.end_row();
+                    ui.label("Scope");
+                    ui.horizontal(|ui| {
+                        changed |= ui.selectable_value(&mut self.scope, ReplaceScope::SelectedColumn, ReplaceScope::SelectedColumn.as_str()).changed();
+                        changed |= ui.selectable_value(&mut self.scope, ReplaceScope::VisibleColumns, ReplaceScope::VisibleColumns.as_str()).changed();
+                    });
+                    ui.end_row();
+                    if matches!(self.scope, ReplaceScope::SelectedColumn) {
+                        ui.label("Column");
+                        let mut selected_column = self.column.clone();
+                        ComboBox::from_id_source("find_replace_column")
+                            .selected_text(selected_column.as_deref().unwrap_or("select a column"))
+                            .show_ui(ui, |ui| {
+                                for column in columns.iter() {
+                                    ui.selectable_value(&mut selected_column, Some(column.name.clone()), column.name.as_str());
+                                }
+                            });
+                        if selected_column != self.column {
+                            self.column = selected_column;
+                            changed = true;
+                        }
+                        ui.end_row();
+                    }
+                    ui.label("Options");
+                    ui.horizontal(|ui| {
+                        changed |= ui.checkbox(&mut self.case_sensitive, "Match case").changed();
+                        changed |= ui.checkbox(&mut self.regex, "Regular expression").changed();
+                        changed |= ui.checkbox(&mut self.filtered_rows_only, "Filtered rows only").changed();
+                    });
+                    ui.end_row();
+                });
+                if changed {
+                    action = FindReplaceAction::Preview;
+                }
+                if let Some(ref error) = self.error {
+                    ui.label(RichText::new(error.lines().last().unwrap_or_default()).color(Color32::DARK_RED));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} cells will be replaced", self.replacements.len()));
+                    if ui.add_enabled(!self.replacements.is_empty(), egui::Button::new("Replace all")).clicked() {
+                        action = FindReplaceAction::Apply;
+                    }
+                });
+                let text_height = ArrayTable::row_height(ui.style(), ui.spacing());
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .max_scroll_height(300.0)
+                    .column(TableColumn::auto().clip(true).resizable(true))
+                    .column(TableColumn::initial(180.0).clip(true).resizable(true))
+                    .column(TableColumn::remainder().clip(true))
+                    .header(text_height, |mut header| {
+                        header.col(|ui| { ui.strong("Pointer"); });
+                        header.col(|ui| { ui.strong("Before"); });
+                        header.col(|ui| { ui.strong("After"); });
+                    })
+                    .body(|body| {
+                        body.rows(text_height, self.replacements.len(), |mut row| {
+                            let replacement = &self.replacements[row.index()];
+                            row.col(|ui| { ui.label(&replacement.pointer.pointer); });
+                            row.col(|ui| { ui.label(&replacement.old_value); });
+                            row.col(|ui| { ui.label(&replacement.new_value); });
+                        });
+                    });
+            });
+        action
+    }
+
+    pub fn compute_replacements(&mut self, nodes: &[JsonArrayEntries<String>], rows: &[usize], parent_pointer: &str, columns: &[&Column]) {
+        self.replacements.clear();
+        self.error = None;
+        if self.find.is_empty() {
+            return;
+        }
+        let options = SearchOptions { case_sensitive: self.case_sensitive, regex: self.regex, ..SearchOptions::default() };
+        let regex = match search_regex(&self.find, &options) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+        let columns = match self.scope {
+            ReplaceScope::SelectedColumn => columns.iter().filter(|c| self.column.as_ref().is_some_and(|name| c.name.eq(name))).copied().collect::<Vec<&Column>>(),
+            ReplaceScope::VisibleColumns => columns.to_vec(),
+        };
+        let parent_pointer = parent_pointer.to_string();
+        for row_index in rows {
+            let row = &nodes[*row_index];
+            for column in columns.iter() {
+                if let Some(entry) = ArrayTable::get_pointer_for_column(&parent_pointer, &&row.entries, row.index, column) {
+                    if !matches!(entry.pointer.value_type, ValueType::String | ValueType::Number | ValueType::Bool) {
+                        continue;
+                    }
+                    if let Some(ref value) = entry.value {
+                        if let Some(new_value) = self.replace_value(&regex, value) {
+                            self.replacements.push(Replacement { row_index: *row_index, pointer: entry.pointer.clone(), old_value: value.clone(), new_value });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn replace_value(&self, regex: &Regex, value: &str) -> Option<String> {
+        if !regex.is_match(value) {
+            return None;
+        }
+        let new_value = if self.regex {
+            regex.replace_all(value, self.replace.as_str())
+        } else {
+            regex.replace_all(value, NoExpand(self.replace.as_str()))
+        };
+        if new_value.eq(value) {
+            None
+        } else {
+            Some(new_value.into_owned())
+        }
+    }
+}
@@ -8,16 +8,36 @@ mod object_table;
 pub mod fonts;
 mod web;
 mod compatibility;
+#[cfg(not(target_arch = "wasm32"))]
+mod export;
+mod find_replace_window;
+mod clipboard;
+mod column_chooser_window;
+mod column_profile_window;
+mod column_conversion;
+mod schema;
+mod record_view;
+mod raw_json_view;
+mod tree_view;
+mod unnest_window;
+#[cfg(not(target_arch = "wasm32"))]
+mod column_widths;
 
 use std::{env, mem};
 
 use std::collections::{BTreeSet};
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
 use std::fs::File;
-use std::io::Read;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{BufRead, BufReader, Read, Write as IoWrite};
 use std::fmt::Write;
 
-use std::path::{PathBuf};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant, SystemTime};
 use crate::components::fps::FrameHistory;
 
 use eframe::{CreationContext};
@@ -25,12 +45,26 @@ use eframe::Theme::Light;
 use egui::{Align2, Button, Color32, ComboBox, Context, CursorIcon, Id, Key, Label, LayerId, Order, RichText, Sense, Separator, TextEdit, TextStyle, Vec2, Widget};
 
 use json_flat_parser::{FlatJsonValue, JSONParser, ParseOptions, ValueType};
-use crate::array_table::{ArrayTable, ScrollToRowMode};
+use crate::array_table::{ArrayTable, FIND_REPLACE_SHORTCUT, ScrollToRowMode, UNDO_SHORTCUT};
 use crate::components::icon;
 use crate::fonts::{CHEVRON_DOWN, CHEVRON_UP};
-use crate::parser::save_to_file;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::export::save_to_xlsx;
+use crate::schema::infer_schema;
+use crate::parser::compression::{Compression, decompress};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::parser::compression::decoder;
+use crate::parser::format::FileFormat;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::parser::{save_to_file, write_json};
 
 pub const ACTIVE_COLOR: Color32 = Color32::from_rgb(63, 142, 252);
+#[cfg(not(target_arch = "wasm32"))]
+const STDIN_FILE: &str = "-";
+#[cfg(not(target_arch = "wasm32"))]
+const STDOUT_ARG: &str = "--stdout";
+#[cfg(not(target_arch = "wasm32"))]
+const FILE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Something to view in the demo windows
 pub trait View<R> {
@@ -69,6 +103,28 @@ impl ArrayResponse {
 fn main() {
     #[cfg(not(target_arch = "wasm32"))]
     {
+        // Usage: json-editor [--stdout] <file|-> [pointer]
+        let mut args: Vec<_> = env::args().collect();
+        let write_to_stdout_on_exit = args.iter().any(|arg| arg == STDOUT_ARG);
+        args.retain(|arg| arg != STDOUT_ARG);
+        // Read stdin before opening the window, so the editor can sit at the end of a pipeline
+        let stdin_content = if args.len() >= 2 && args[1] == STDIN_FILE {
+            let mut content = Vec::new();
+            if let Err(e) = std::io::stdin().lock().read_to_end(&mut content) {
+                eprintln!("Failed to read stdin: {}", e);
+                std::process::exit(1);
+            }
+            // Compressed input is checked once decompressed
+            if matches!(Compression::detect(content.as_slice()), Compression::None) {
+                if let Err(e) = std::str::from_utf8(content.as_slice()) {
+                    eprintln!("Failed to read stdin: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            Some(content)
+        } else {
+            None
+        };
         let options = eframe::NativeOptions {
             default_theme: Light,
             persist_window: false,
@@ -76,7 +132,7 @@ fn main() {
             // viewport: egui::ViewportBuilder::default().with_inner_size(Vec2 { x: 1900.0, y: 1200.0 }).with_maximized(true),
             ..eframe::NativeOptions::default()
         };
-        eframe::run_native("JSON table editor", options, Box::new(|cc| {
+        eframe::run_native("JSON table editor", options, Box::new(move |cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
             let mut style = (*cc.egui_ctx.style()).clone();
             style.spacing.scroll.floating = false;
@@ -84,12 +140,13 @@ fn main() {
             style.spacing.scroll.bar_inner_margin = 6.0;
             cc.egui_ctx.set_style(style);
             let mut app = MyApp::new(cc);
+            app.write_to_stdout_on_exit = write_to_stdout_on_exit;
 
-            let args: Vec<_> = env::args().collect();
             if args.len() >= 2 {
-                println!("Opening {}", args[1].as_str());
+                log!("Opening {}", args[1].as_str());
                 app.selected_file = Some(PathBuf::from(args[1].as_str()));
                 app.should_parse_again = true;
+                app.stdin_content = stdin_content;
             }
             if args.len() >= 3 {
                 app.selected_pointer = Some(args[2].clone());
@@ -99,6 +156,82 @@ fn main() {
     }
 }
 
+enum ParsedContent {
+    Table { table: Box<ArrayTable>, depth: u8, max_depth: u8 },
+    // Root is not an array, pointers of arrays user can choose to parse
+    NotAnArray(Vec<String>),
+}
+
+/// Content of a file, decompressed and converted to json.
+#[cfg(not(target_arch = "wasm32"))]
+struct LoadedFile {
+    json: String,
+    format: FileFormat,
+    compression: Compression,
+    modified_at: Option<SystemTime>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_file(path: &Path) -> Result<LoadedFile, String> {
+    let format = FileFormat::from_path(path);
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let metadata = file.metadata().map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(file);
+    let compression = Compression::detect(reader.fill_buf().map_err(|e| e.to_string())?);
+    let mut content = String::with_capacity(metadata.len() as usize);
+    // let mut reader = LfToCrlfReader::new(file);
+    // reader.read_to_string(&mut content);
+    decoder(reader, compression).and_then(|mut reader| reader.read_to_string(&mut content)).map_err(|e| e.to_string())?;
+    if !matches!(format, FileFormat::Json) {
+        content = crate::parser::format::to_json(&content, format).map_err(|e| format!("failed to convert {} to json: {}", format.as_str(), e))?;
+    }
+    Ok(LoadedFile { json: content, format, compression, modified_at: metadata.modified().ok() })
+}
+
+/// In memory json content decompressed, e.g. stdin content.
+#[cfg(not(target_arch = "wasm32"))]
+fn decompress_content(content: &[u8]) -> Result<LoadedFile, String> {
+    let compression = Compression::detect(content);
+    let json = decompress(content).map_err(|e| e.to_string())?;
+    let json = String::from_utf8(json).map_err(|e| e.to_string())?;
+    Ok(LoadedFile { json, format: FileFormat::Json, compression, modified_at: None })
+}
+
+/// File read and decompressed on a loading thread, UI keeps running until it is loaded.
+#[cfg(not(target_arch = "wasm32"))]
+struct FileLoading {
+    path: PathBuf,
+    // Loaded content replaces current table, keeping its view state
+    reload: bool,
+    loaded: Arc<Mutex<Option<Result<LoadedFile, String>>>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileLoading {
+    fn spawn(path: PathBuf, reload: bool, load: impl FnOnce() -> Result<LoadedFile, String> + Send + 'static) -> Self {
+        let loaded = Arc::new(Mutex::new(None));
+        let loaded_ref = loaded.clone();
+        std::thread::spawn(move || {
+            let result = load();
+            *loaded_ref.lock().unwrap() = Some(result);
+        });
+        Self { path, reload, loaded }
+    }
+}
+
+/// Nested values of large files are parsed on demand, when depth is increased.
+#[cfg(not(target_arch = "wasm32"))]
+fn initial_max_depth(json_len: usize) -> u8 {
+    // Size after decompression
+    let size = json_len / 1024 / 1024;
+    if size < 100 {
+        // 1
+        u8::MAX
+    } else {
+        1 // should start after prefix
+    }
+}
+
 struct MyApp {
     frame_history: FrameHistory,
     table: Option<ArrayTable>,
@@ -115,6 +248,26 @@ struct MyApp {
     unsaved_changes: bool,
     show_fps: bool,
     web_loaded_json: Arc<Mutex<Option<Vec<u8>>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    save_as_format: FileFormat,
+    #[cfg(not(target_arch = "wasm32"))]
+    compression: Compression,
+    #[cfg(not(target_arch = "wasm32"))]
+    stdin_content: Option<Vec<u8>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    write_to_stdout_on_exit: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    file_modified_at: Option<SystemTime>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_file_modified_at: Option<SystemTime>,
+    #[cfg(not(target_arch = "wasm32"))]
+    last_file_check: Instant,
+    #[cfg(not(target_arch = "wasm32"))]
+    file_changed_externally: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    loading_file: Option<FileLoading>,
+    // Last failure to open or reload file
+    load_error: Option<String>,
 }
 
 impl MyApp {
@@ -148,6 +301,25 @@ impl MyApp {
             unsaved_changes: false,
             show_fps: true,
             web_loaded_json: Arc::new(Mutex::new(None)),
+            #[cfg(not(target_arch = "wasm32"))]
+            save_as_format: FileFormat::Json,
+            #[cfg(not(target_arch = "wasm32"))]
+            compression: Compression::None,
+            #[cfg(not(target_arch = "wasm32"))]
+            stdin_content: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            write_to_stdout_on_exit: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            file_modified_at: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_file_modified_at: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            last_file_check: Instant::now(),
+            #[cfg(not(target_arch = "wasm32"))]
+            file_changed_externally: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            loading_file: None,
+            load_error: None,
         }
     }
     pub fn windows(&mut self, ctx: &Context) {
@@ -159,26 +331,229 @@ impl MyApp {
         }
     }
 
+    /// Start loading selected file, it is opened once loaded.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn open_json(&mut self) {
-        let mut file = File::open(self.selected_file.as_ref().unwrap()).unwrap();
-        let metadata1 = file.metadata().unwrap();
+        if self.loading_file.is_some() {
+            return;
+        }
+        if self.is_stdin() {
+            self.open_stdin_content();
+            return;
+        }
+        let path = self.selected_file.clone().unwrap();
+        let file_path = path.clone();
+        self.loading_file = Some(FileLoading::spawn(path, false, move || read_file(&file_path)));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_loaded_file(&mut self, loaded: Result<LoadedFile, String>) {
+        match loaded {
+            Ok(loaded) => {
+                self.save_as_format = loaded.format;
+                self.compression = loaded.compression;
+                self.file_modified_at = loaded.modified_at;
+                self.pending_file_modified_at = None;
+                self.file_changed_externally = false;
+                let max_depth = if self.is_stdin() { u8::MAX } else { initial_max_depth(loaded.json.len()) };
+                self.open_json_content(max_depth, loaded.json.as_bytes());
+            }
+            Err(e) => {
+                let name = if self.is_stdin() { "stdin".to_string() } else { self.selected_file.as_ref().map(|path| path.display().to_string()).unwrap_or_default() };
+                log!("Failed to open {}: {}", name, e);
+                self.load_error = Some(format!("Failed to open {}: {}", name, e));
+                self.selected_file = None;
+                self.should_parse_again = false;
+            }
+        }
+    }
 
-        let size = (metadata1.len() / 1024 / 1024) as usize;
-        let max_depth = if size < 100 {
-            // 1
-            u8::MAX
+    /// Open loaded file once loading thread is done.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn poll_file_loading(&mut self, ctx: &Context) {
+        let Some(loading) = self.loading_file.as_ref() else {
+            return;
+        };
+        let Some(loaded) = loading.loaded.lock().unwrap().take() else {
+            ctx.request_repaint();
+            return;
+        };
+        let loading = self.loading_file.take().unwrap();
+        // Another file was selected meanwhile
+        if self.selected_file.as_ref() != Some(&loading.path) {
+            return;
+        }
+        if loading.reload {
+            self.reload_loaded_file(loaded);
+        } else {
+            self.open_loaded_file(loaded);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_stdin_content(&mut self) {
+        let stdin_content = mem::take(&mut self.stdin_content).unwrap_or_default();
+        if matches!(Compression::detect(stdin_content.as_slice()), Compression::None) {
+            self.compression = Compression::None;
+            self.open_json_content(u8::MAX, stdin_content.as_slice());
+        } else {
+            let content = stdin_content.clone();
+            self.loading_file = Some(FileLoading::spawn(PathBuf::from(STDIN_FILE), false, move || decompress_content(content.as_slice())));
+        }
+        // Keep content to be able to parse again at another pointer
+        self.stdin_content = Some(stdin_content);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn read_file_modified_at(&self) -> Option<SystemTime> {
+        if self.is_stdin() {
+            return None;
+        }
+        self.selected_file.as_ref().and_then(|path| fs::metadata(path).ok()).and_then(|metadata| metadata.modified().ok())
+    }
+
+    /// Poll modification time of opened file, reload it when there is no unsaved changes, otherwise let user decide.
+    /// A change is only considered once modification time is stable between two checks, so we don't read a file being written.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn check_file_changed(&mut self, ctx: &Context) {
+        if self.table.is_none() || self.file_modified_at.is_none() || self.file_changed_externally || self.loading_file.is_some() {
+            return;
+        }
+        ctx.request_repaint_after(FILE_CHECK_INTERVAL);
+        if self.last_file_check.elapsed() < FILE_CHECK_INTERVAL {
+            return;
+        }
+        self.last_file_check = Instant::now();
+        let modified_at = self.read_file_modified_at();
+        if modified_at.is_none() || modified_at == self.file_modified_at {
+            self.pending_file_modified_at = None;
+            return;
+        }
+        if self.pending_file_modified_at != modified_at {
+            self.pending_file_modified_at = modified_at;
+            return;
+        }
+        if self.unsaved_changes {
+            self.file_changed_externally = true;
         } else {
-            1 // should start after prefix
+            log!("{} changed on disk, reloading", self.selected_file.as_ref().unwrap().display());
+            self.reload();
+        }
+    }
+
+    /// Load file again, then parse it keeping depth, pinned columns, filters and scroll position of current table.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload(&mut self) {
+        if self.table.is_none() || self.is_stdin() || self.loading_file.is_some() {
+            return;
+        }
+        let Some(path) = self.selected_file.clone() else {
+            return;
         };
-        let mut content = String::with_capacity(metadata1.len() as usize);
-        // let mut reader = LfToCrlfReader::new(file);
-        // reader.read_to_string(&mut content);
-        file.read_to_string(&mut content).unwrap();
+        self.file_changed_externally = false;
+        self.pending_file_modified_at = None;
+        let file_path = path.clone();
+        self.loading_file = Some(FileLoading::spawn(path, true, move || read_file(&file_path)));
+    }
+
+    /// Current table and file are kept when file can't be read or parsed.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_loaded_file(&mut self, loaded: Result<LoadedFile, String>) {
+        let (Some(table), Some(path)) = (self.table.as_ref(), self.selected_file.clone()) else {
+            return;
+        };
+        let state = table.view_state();
+        let parent_pointer = Some(table.parent_pointer.clone()).filter(|pointer| !pointer.is_empty());
+        let reloaded = loaded.and_then(|loaded| {
+            match Self::parse_content(initial_max_depth(loaded.json.len()), loaded.json.as_bytes(), parent_pointer.as_ref())? {
+                ParsedContent::Table { table, depth, max_depth } => Ok((loaded, *table, depth, max_depth)),
+                ParsedContent::NotAnArray(_) => Err("root is not an array anymore".to_string()),
+            }
+        });
+        match reloaded {
+            Ok((loaded, table, depth, max_depth)) => {
+                self.save_as_format = loaded.format;
+                self.compression = loaded.compression;
+                self.file_modified_at = loaded.modified_at;
+                self.load_error = None;
+                self.set_table(table, depth, max_depth);
+                let table = self.table.as_mut().unwrap();
+                let depth = state.depth.clamp(self.min_depth, self.max_depth);
+                if depth != self.depth {
+                    self.depth = depth;
+                    if let Some(new_max_depth) = table.update_max_depth(depth) {
+                        self.max_depth = new_max_depth as u8;
+                    }
+                }
+                table.restore_view_state(state);
+            }
+            Err(e) => {
+                log!("Failed to reload {}: {}", path.display(), e);
+                self.load_error = Some(format!("Failed to reload {}: {}", path.display(), e));
+                // Wait for next change before trying again
+                self.file_modified_at = self.read_file_modified_at();
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_column_widths(&mut self) {
+        if self.is_stdin() {
+            return;
+        }
+        if let (Some(path), Some(table)) = (self.selected_file.as_ref(), self.table.as_mut()) {
+            table.set_column_widths(crate::column_widths::load(path));
+        }
+    }
 
-        self.open_json_content(max_depth, content.as_bytes());
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_column_widths(&mut self) {
+        if self.is_stdin() {
+            return;
+        }
+        if let (Some(path), Some(table)) = (self.selected_file.as_ref(), self.table.as_mut()) {
+            table.column_widths_changed = false;
+            if let Err(e) = crate::column_widths::save(path, table.column_widths()) {
+                log!("Failed to save column widths of {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    #[inline]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn is_stdin(&self) -> bool {
+        self.selected_file.as_ref().is_some_and(|path| path.as_os_str() == STDIN_FILE)
     }
 
     fn open_json_content(&mut self, max_depth: u8, json: &[u8]) {
+        match Self::parse_content(max_depth, json, self.selected_pointer.as_ref()) {
+            Ok(ParsedContent::Table { table, depth, max_depth }) => {
+                self.set_table(*table, depth, max_depth);
+                self.load_error = None;
+            }
+            Ok(ParsedContent::NotAnArray(pointers)) => {
+                self.should_parse_again = true;
+                self.parsing_invalid = true;
+                self.unsaved_changes = false;
+                #[cfg(target_arch = "wasm32")]
+                {
+                    let mut json_guard = self.web_loaded_json.lock().unwrap();
+                    *json_guard = Some(json.to_vec());
+                }
+                self.parsing_invalid_pointers = pointers;
+            }
+            Err(e) => {
+                log!("Failed to parse json: {}", e);
+                self.load_error = Some(format!("Failed to parse json: {}", e));
+                self.selected_file = None;
+                self.selected_pointer = None;
+                self.should_parse_again = false;
+            }
+        }
+    }
+
+    /// Parse json as a table when it is an array or `selected_pointer` is set, otherwise list its arrays.
+    fn parse_content(max_depth: u8, json: &[u8], selected_pointer: Option<&String>) -> Result<ParsedContent, String> {
         let mut found_array = false;
         let size = json.len() / 1024 / 1024;
         log!("open_json_content with size {}mb, found array {}", size, found_array);
@@ -191,56 +566,49 @@ impl MyApp {
                 break;
             }
         }
-        if found_array || self.selected_pointer.is_some() {
+        if found_array || selected_pointer.is_some() {
             let start = crate::compatibility::now();
             let mut options = ParseOptions::default().parse_array(false).max_depth(max_depth);
-            if let Some(ref start_at) = self.selected_pointer {
+            if let Some(start_at) = selected_pointer {
                 options = options.start_parse_at(start_at.clone());
             }
-            let parse_result = JSONParser::parse_bytes(json, options);
-
-            let result = parse_result.unwrap().to_owned();
+            let result = JSONParser::parse_bytes(json, options).map_err(|e| e.to_string())?.to_owned();
             let parsing_max_depth = result.parsing_max_depth;
             log!("Custom parser took {}ms for a {}mb file, max depth {}, {}", start.elapsed().as_millis(), size, parsing_max_depth, result.json.len());
             let parse_result = result.clone_except_json();
 
             let start = crate::compatibility::now();
-            let (result1, columns) = crate::parser::as_array(result).unwrap();
+            let (result1, columns) = crate::parser::as_array(result)?;
             log!("Transformation to array took {}ms, root array len {}, columns {}", start.elapsed().as_millis(), result1.len(), columns.len());
 
             let max_depth = parse_result.max_json_depth;
             let depth = (parse_result.depth_after_start_at + 1).min(parsing_max_depth);
-            let mut prefix = "".to_owned();
-            if let Some(ref start_at) = self.selected_pointer {
-                prefix = start_at.clone();
-            }
+            let prefix = selected_pointer.cloned().unwrap_or_default();
             let table = ArrayTable::new(Some(parse_result), result1, columns, depth, prefix);
-            self.table = Some(table);
-            self.depth = depth;
-            self.max_depth = max_depth as u8;
-            self.min_depth = depth;
-            self.parsing_invalid_pointers.clear();
-            self.should_parse_again = false;
-            self.parsing_invalid = false;
-            self.selected_pointer = None;
-            self.unsaved_changes = false;
+            Ok(ParsedContent::Table { table: Box::new(table), depth, max_depth: max_depth as u8 })
         } else {
             let options = ParseOptions::default().parse_array(false).max_depth(max_depth);
-            let result = JSONParser::parse_bytes(json, options.clone()).unwrap();
-            self.should_parse_again = true;
-            self.parsing_invalid = true;
-            self.unsaved_changes = false;
-            #[cfg(target_arch = "wasm32")]
-            {
-                let mut json_guard = self.web_loaded_json.lock().unwrap();
-                *json_guard = Some(json.to_vec());
-            }
-            self.parsing_invalid_pointers = result.json.iter()
+            let result = JSONParser::parse_bytes(json, options).map_err(|e| e.to_string())?;
+            Ok(ParsedContent::NotAnArray(result.json.iter()
                 .filter(|entry| matches!(entry.pointer.value_type, ValueType::Array(_)))
-                .map(|entry| entry.pointer.pointer.clone()).collect();
+                .map(|entry| entry.pointer.pointer.clone()).collect()))
         }
     }
 
+    fn set_table(&mut self, table: ArrayTable, depth: u8, max_depth: u8) {
+        self.table = Some(table);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.load_column_widths();
+        self.depth = depth;
+        self.max_depth = max_depth;
+        self.min_depth = depth;
+        self.parsing_invalid_pointers.clear();
+        self.should_parse_again = false;
+        self.parsing_invalid = false;
+        self.selected_pointer = None;
+        self.unsaved_changes = false;
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn file_picker(&mut self) {
         if let Some(path) = rfd::FileDialog::new().pick_file() {
@@ -304,10 +672,23 @@ fn set_open(open: &mut BTreeSet<String>, key: &'static str, is_open: bool) {
 }
 
 impl eframe::App for MyApp {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if !self.write_to_stdout_on_exit {
+            return;
+        }
+        if let Some(ref table) = self.table {
+            let mut stdout = std::io::stdout().lock();
+            if let Err(e) = write_json(table.parent_pointer.as_str(), table.nodes(), &mut stdout).and_then(|_| stdout.flush()) {
+                log!("Failed to write to stdout: {}", e);
+            }
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         #[cfg(not(target_arch = "wasm32"))] {
             let mut title = format!("json table editor - {}{}",
-                                    self.selected_file.as_ref().map(|p| p.display().to_string()).unwrap_or("No file selected".to_string()),
+                                    if self.is_stdin() { "stdin".to_string() } else { self.selected_file.as_ref().map(|p| p.display().to_string()).unwrap_or("No file selected".to_string()) },
                                     if self.unsaved_changes { " *" } else { "" }
             );
 
@@ -319,6 +700,10 @@ impl eframe::App for MyApp {
 
             ctx.send_viewport_cmd_to(ctx.parent_viewport_id(), egui::ViewportCommand::Title(title));
         }
+        #[cfg(not(target_arch = "wasm32"))] {
+            self.poll_file_loading(ctx);
+            self.check_file_changed(ctx);
+        }
         self.windows(ctx);
         egui::TopBottomPanel::top("top").show(ctx, |ui| {
             ui.horizontal_wrapped(|ui| {
@@ -332,30 +717,135 @@ impl eframe::App for MyApp {
                                 self.file_picker();
                             }
                             ui.separator();
-                            if ui.button("Save").clicked() {
+                            if ui.add_enabled(!self.is_stdin() && self.selected_file.is_some(), Button::new("Save")).clicked() {
                                 ui.close_menu();
-                                let table = self.table.as_ref().unwrap();
-                                save_to_file(table.parent_pointer.as_str(), table.nodes(), self.selected_file.as_ref().unwrap()).unwrap();
-                                self.unsaved_changes = false;
+                                if let (Some(table), Some(path)) = (self.table.as_ref(), self.selected_file.as_ref()) {
+                                    if let Err(e) = save_to_file(table.parent_pointer.as_str(), table.nodes(), path, FileFormat::from_path(path), self.compression) {
+                                        log!("Failed to save {}: {}", path.display(), e);
+                                    } else {
+                                        self.unsaved_changes = false;
+                                        self.file_modified_at = self.read_file_modified_at();
+                                        self.file_changed_externally = false;
+                                    }
+                                }
                             }
                             ui.separator();
-                            if ui.button("Save as").clicked() {
+                            ui.horizontal(|ui| {
+                                if ui.button("Save as").clicked() {
+                                    ui.close_menu();
+                                    if let Some(mut path) = rfd::FileDialog::new().add_filter(self.save_as_format.as_str(), self.save_as_format.extensions()).save_file() {
+                                        if path.extension().is_none() {
+                                            path.set_extension(self.save_as_format.extensions()[0]);
+                                        }
+                                        let table = self.table.as_ref().unwrap();
+                                        let compression = Compression::from_path(&path);
+                                        if let Err(e) = save_to_file(table.parent_pointer.as_str(), table.nodes(), &path, self.save_as_format, compression) {
+                                            log!("Failed to save {}: {}", path.display(), e);
+                                        } else {
+                                            self.compression = compression;
+                                            self.selected_file = Some(path);
+                                            self.unsaved_changes = false;
+                                            self.file_modified_at = self.read_file_modified_at();
+                                            self.file_changed_externally = false;
+                                        }
+                                    }
+                                }
+                                for format in FileFormat::ALL {
+                                    ui.selectable_value(&mut self.save_as_format, format, format.as_str());
+                                }
+                            });
+                            ui.separator();
+                            ui.checkbox(&mut self.write_to_stdout_on_exit, "Write to stdout on exit");
+                            ui.separator();
+                            if ui.button("Export as xlsx").clicked() {
                                 ui.close_menu();
-                                if let Some(path) = rfd::FileDialog::new().save_file() {
-                                    self.selected_file = Some(path);
+                                if let Some(path) = rfd::FileDialog::new().add_filter("Excel workbook", &["xlsx"]).save_file() {
                                     let table = self.table.as_ref().unwrap();
-                                    save_to_file(table.parent_pointer.as_str(), table.nodes(), self.selected_file.as_ref().unwrap()).unwrap();
-                                    self.unsaved_changes = false;
+                                    if let Err(e) = save_to_xlsx(table.parent_pointer.as_str(), table.nodes(), table.filtered_nodes(),
+                                                                 table.column_pinned(), table.column_selected(), &path) {
+                                        log!("Failed to export {}: {}", path.display(), e);
+                                    }
                                 }
                             }
                         });
                     }
+                    ui.menu_button("Edit", |ui| {
+                        ui.set_min_width(220.0);
+                        let table = self.table.as_mut().unwrap();
+                        if ui.add_enabled(table.can_undo(), Button::new("Undo").shortcut_text(ui.ctx().format_shortcut(&UNDO_SHORTCUT))).clicked() {
+                            ui.close_menu();
+                            if table.undo().is_some() {
+                                self.unsaved_changes = true;
+                            }
+                        }
+                        ui.separator();
+                        if ui.add(Button::new("Find and replace").shortcut_text(ui.ctx().format_shortcut(&FIND_REPLACE_SHORTCUT))).clicked() {
+                            ui.close_menu();
+                            table.open_find_replace();
+                        }
+                    });
+                    ui.menu_button("Schema", |ui| {
+                        ui.set_min_width(220.0);
+                        let table = self.table.as_mut().unwrap();
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button("Attach JSON schema").clicked() {
+                            ui.close_menu();
+                            if let Some(path) = rfd::FileDialog::new().add_filter("JSON schema", &["json"]).pick_file() {
+                                let schema = fs::read_to_string(&path).map_err(|e| e.to_string())
+                                    .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+                                    .and_then(|schema| table.attach_schema(schema));
+                                if let Err(e) = schema {
+                                    log!("Failed to attach schema {}: {}", path.display(), e);
+                                }
+                            }
+                        }
+                        if ui.add_enabled(table.has_schema(), Button::new("Show schema violations")).clicked() {
+                            ui.close_menu();
+                            table.show_violations = true;
+                        }
+                        if ui.add_enabled(table.has_schema(), Button::new("Detach JSON schema")).clicked() {
+                            ui.close_menu();
+                            table.detach_schema();
+                        }
+                        ui.separator();
+                        if ui.button("Copy inferred JSON schema").clicked() {
+                            ui.close_menu();
+                            let schema = infer_schema(table.parent_pointer.as_str(), table.nodes(), table.all_columns());
+                            ui.ctx().copy_text(serde_json::to_string_pretty(&schema).unwrap());
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button("Export inferred JSON schema").clicked() {
+                            ui.close_menu();
+                            if let Some(path) = rfd::FileDialog::new().add_filter("JSON schema", &["json"]).save_file() {
+                                let schema = infer_schema(table.parent_pointer.as_str(), table.nodes(), table.all_columns());
+                                if let Err(e) = fs::write(&path, serde_json::to_string_pretty(&schema).unwrap()) {
+                                    log!("Failed to export {}: {}", path.display(), e);
+                                }
+                            }
+                        }
+                    });
+                    ui.menu_button("View", |ui| {
+                        ui.set_min_width(220.0);
+                        let table = self.table.as_mut().unwrap();
+                        if ui.checkbox(&mut table.show_record_view, "Record view").clicked() {
+                            ui.close_menu();
+                        }
+                        if ui.checkbox(&mut table.show_raw_json, "Raw JSON").clicked() {
+                            ui.close_menu();
+                        }
+                        if ui.checkbox(&mut table.show_tree, "Tree").clicked() {
+                            ui.close_menu();
+                        }
+                    });
                 }
                 if let Some(ref mut table) = self.table {
                     ui.separator();
                     let change_depth_slider_response = ui.add(
                         egui::Slider::new(&mut self.depth, self.min_depth..=self.max_depth).text("Depth"),
                     );
+                    if ui.button("Columns").on_hover_text("Show, hide and reorder columns").clicked() {
+                        table.open_column_chooser();
+                    }
                     ui.add(Separator::default().vertical());
                     let scroll_to_column_response = ui.allocate_ui(Vec2::new(180.0, ui.spacing().interact_size.y), |ui| {
                         ui.horizontal(|ui| {
@@ -394,7 +884,7 @@ impl eframe::App for MyApp {
                             });
                             let hint_text = match &table.scroll_to_row_mode {
                                 ScrollToRowMode::RowNumber => "Type row number",
-                                ScrollToRowMode::MatchingTerm => "Type term contained in string value"
+                                ScrollToRowMode::MatchingTerm => "Type term contained in value"
                             };
                             let text_edit = TextEdit::singleline(&mut table.scroll_to_row).hint_text(hint_text);
                             let scroll_to_row_response = ui.add(text_edit);
@@ -419,6 +909,36 @@ impl eframe::App for MyApp {
                         }).inner
                     }).inner;
 
+                    if matches!(table.scroll_to_row_mode, ScrollToRowMode::MatchingTerm) {
+                        let search_options_changed = ui.horizontal(|ui| {
+                            let mut changed = ui.toggle_value(&mut table.search_options.case_sensitive, "Aa").on_hover_text("Match case").changed();
+                            changed |= ui.toggle_value(&mut table.search_options.regex, ".*").on_hover_text("Regular expression").changed();
+                            changed |= ui.toggle_value(&mut table.search_options.match_keys, "Keys").on_hover_text("Match keys as well as values").changed();
+                            let mut search_column = table.search_options.column.clone();
+                            ComboBox::from_id_source("search_column")
+                                .selected_text(search_column.as_deref().unwrap_or("all columns"))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut search_column, None, "all columns");
+                                    for column in table.column_pinned().iter().skip(1).chain(table.column_selected().iter()) {
+                                        if !column.name.is_empty() {
+                                            ui.selectable_value(&mut search_column, Some(column.name.clone()), column.name.as_str());
+                                        }
+                                    }
+                                });
+                            if search_column != table.search_options.column {
+                                table.search_options.column = search_column;
+                                changed = true;
+                            }
+                            if let Some(ref search_error) = table.search_error {
+                                ui.label(RichText::new(search_error.lines().last().unwrap_or_default()).color(Color32::DARK_RED));
+                            }
+                            changed
+                        }).inner;
+                        if search_options_changed {
+                            table.refresh_search();
+                        }
+                    }
+
 
                     // interaction handling
                     if scroll_to_column_response.changed() {
@@ -447,6 +967,32 @@ impl eframe::App for MyApp {
             });
         });
 
+        if let Some(error) = self.load_error.clone() {
+            egui::TopBottomPanel::top("load-error").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(error).color(Color32::DARK_RED));
+                    if ui.button("Dismiss").clicked() {
+                        self.load_error = None;
+                    }
+                });
+            });
+        }
+        #[cfg(not(target_arch = "wasm32"))] {
+            if self.file_changed_externally {
+                egui::TopBottomPanel::top("file-changed").show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("File has been modified by another program, reloading it will discard unsaved changes").color(Color32::DARK_RED));
+                        if ui.button("Reload").clicked() {
+                            self.reload();
+                        }
+                        if ui.button("Ignore").clicked() {
+                            self.file_changed_externally = false;
+                            self.file_modified_at = self.read_file_modified_at();
+                        }
+                    });
+                });
+            }
+        }
         if self.table.is_some() {
             let table = self.table.as_ref().unwrap();
             egui::TopBottomPanel::bottom("bottom-panel").show(ctx, |ui| {
@@ -514,7 +1060,25 @@ impl eframe::App for MyApp {
                     self.parsing_invalid = false;
                     self.parsing_invalid_pointers.clear();
                     if let Some(bytes) = file.bytes {
-                        self.open_json_content(u8::MAX, bytes.as_ref());
+                        let format = FileFormat::from_path(Path::new(&file.name));
+                        let compression = Compression::detect(bytes.as_ref());
+                        #[cfg(not(target_arch = "wasm32"))] {
+                            self.save_as_format = format;
+                            self.compression = compression;
+                        }
+                        let bytes = if matches!(compression, Compression::None) {
+                            Ok(bytes.to_vec())
+                        } else {
+                            decompress(bytes.as_ref())
+                        };
+                        match bytes {
+                            Ok(bytes) if matches!(format, FileFormat::Json) => self.open_json_content(u8::MAX, bytes.as_slice()),
+                            Ok(bytes) => match crate::parser::format::to_json(&String::from_utf8_lossy(bytes.as_slice()), format) {
+                                Ok(json) => self.open_json_content(u8::MAX, json.as_bytes()),
+                                Err(e) => log!("Failed to convert {} to json: {}", format.as_str(), e)
+                            },
+                            Err(e) => log!("Failed to decompress {}: {}", file.name, e)
+                        }
                     } else {
                         self.selected_file = Some(file.path.unwrap());
                     }
@@ -526,6 +1090,10 @@ impl eframe::App for MyApp {
                 if response1.edited_value.is_some() {
                     self.unsaved_changes = true;
                 }
+                #[cfg(not(target_arch = "wasm32"))]
+                if table.column_widths_changed && !ui.input(|i| i.pointer.any_down()) {
+                    self.save_column_widths();
+                }
             } else if self.selected_file.is_none() {
                 ui.allocate_ui_at_rect(ui.max_rect(),
                                        |ui| {
@@ -591,6 +1159,12 @@ impl eframe::App for MyApp {
                 } else if self.should_parse_again {
                     #[cfg(not(target_arch = "wasm32"))] {
                         self.open_json();
+                        if self.loading_file.is_some() {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label("Loading file...");
+                            });
+                        }
                     }
                     #[cfg(target_arch = "wasm32")] {
                         self.web_try_open_json_bytes();
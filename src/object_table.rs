@@ -1,15 +1,23 @@
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::mem;
 use egui::scroll_area::ScrollBarVisibility;
-use egui::{Id, Key, Label, Sense, TextEdit};
+use egui::{Id, Key, Label, RichText, Sense, TextEdit};
 use json_flat_parser::{FlatJsonValue, PointerKey, ValueType};
 use json_flat_parser::serializer::serialize_to_json_with_option;
-use crate::ArrayResponse;
+use crate::{ArrayResponse, concat_string};
+use crate::components::icon;
+use crate::fonts::{CARET_DOWN, CARET_RIGHT};
 
 pub struct ObjectTable {
     pub nodes: Vec<FlatJsonValue<String>>,
     filtered_nodes: Vec<usize>,
     arrays: Vec<FlatJsonValue<String>>,
+    // Nested objects and arrays are displayed and can be collapsed, otherwise only values are displayed
+    collapsible: bool,
+    // Objects and arrays whose content is hidden
+    collapsed: HashSet<String>,
+    min_depth: u8,
 
     // Handling interaction
 
@@ -20,23 +28,69 @@ pub struct ObjectTable {
 
 impl ObjectTable {
     pub fn new(nodes: Vec<FlatJsonValue<String>>) -> Self {
-        let mut filtered_nodes = Vec::with_capacity(nodes.len());
-        let mut arrays = vec![];
-        for (index, entry) in nodes.iter().enumerate() {
-            if !matches!(entry.pointer.value_type, ValueType::Array(_)) && !matches!(entry.pointer.value_type, ValueType::Object(_)) {
-                filtered_nodes.push(index);
-            } else if matches!(entry.pointer.value_type, ValueType::Array(_)) {
-                arrays.push(entry.clone());
-            }
-        }
-        Self {
+        let arrays = nodes.iter().filter(|entry| matches!(entry.pointer.value_type, ValueType::Array(_))).cloned().collect();
+        let mut table = Self {
             nodes,
-            filtered_nodes,
+            filtered_nodes: vec![],
             arrays,
+            collapsible: false,
+            collapsed: HashSet::new(),
+            min_depth: 0,
             editing_index: RefCell::new(None),
             editing_value: RefCell::new("".to_string()),
             focused_cell: None,
+        };
+        table.refresh_filtered_nodes();
+        table
+    }
+
+    /// Display nested objects and arrays, indented by depth, with their content collapsible.
+    pub fn collapsible(mut self) -> Self {
+        self.collapsible = true;
+        self.refresh_filtered_nodes();
+        self.min_depth = self.filtered_nodes.iter().map(|index| self.nodes[*index].pointer.depth).min().unwrap_or(0);
+        self
+    }
+
+    #[inline]
+    fn is_container(entry: &FlatJsonValue<String>) -> bool {
+        matches!(entry.pointer.value_type, ValueType::Array(_)) || matches!(entry.pointer.value_type, ValueType::Object(_))
+    }
+
+    /// Displayed entries: values, and when collapsible, nested objects and arrays except root one.
+    fn refresh_filtered_nodes(&mut self) {
+        if !self.collapsible {
+            self.filtered_nodes = self.nodes.iter().enumerate().filter(|(_, entry)| !Self::is_container(entry)).map(|(index, _)| index).collect();
+            return;
+        }
+        // Root is the shallowest container, when every entry is within it
+        let root = self.nodes.iter().enumerate().filter(|(_, entry)| Self::is_container(entry)).min_by_key(|(_, entry)| entry.pointer.depth)
+            .filter(|(_, root)| self.nodes.iter().all(|other| other.pointer.pointer.starts_with(&root.pointer.pointer)))
+            .map(|(index, _)| index);
+        let collapsed_prefixes = self.collapsed.iter().map(|pointer| concat_string!(pointer, "/")).collect::<Vec<String>>();
+        self.filtered_nodes = self.nodes.iter().enumerate()
+            .filter(|(index, _)| root != Some(*index))
+            .filter(|(_, entry)| !collapsed_prefixes.iter().any(|prefix| entry.pointer.pointer.starts_with(prefix)))
+            .map(|(index, _)| index)
+            .collect();
+    }
+
+    pub fn collapsed(&self) -> &HashSet<String> {
+        &self.collapsed
+    }
+
+    /// Collapse again objects and arrays collapsed in a previous table of same content.
+    pub fn with_collapsed(mut self, collapsed: HashSet<String>) -> Self {
+        self.collapsed = collapsed;
+        self.refresh_filtered_nodes();
+        self
+    }
+
+    fn toggle_collapsed(&mut self, pointer: String) {
+        if !self.collapsed.remove(&pointer) {
+            self.collapsed.insert(pointer);
         }
+        self.refresh_filtered_nodes();
     }
 
     fn table_ui(&mut self, ui: &mut egui::Ui, _pinned: bool) -> ArrayResponse {
@@ -65,12 +119,36 @@ impl ObjectTable {
                 header.col(|ui, _| { Some(ui.label("Value")) });
             }).body(None, None, self.focused_cell, |body| {
             let mut updated_value: Option<(PointerKey, String)> = None;
+            let mut toggled_pointer: Option<String> = None;
             body.rows(text_height, self.filtered_nodes.len(), |mut row| {
                 let table_row_index = row.index();
                 let row_index = self.filtered_nodes[table_row_index];
                 let entry = &self.nodes[row_index];
-                row.col(|c, _| Some(c.label(&entry.pointer.pointer)));
+                let is_container = Self::is_container(entry);
+                row.col(|c, _| {
+                    if self.collapsible {
+                        c.add_space(entry.pointer.depth.saturating_sub(self.min_depth) as f32 * c.spacing().indent);
+                    }
+                    if is_container {
+                        let collapsed = self.collapsed.contains(&entry.pointer.pointer);
+                        let response = c.add(Label::new(icon::icon(if collapsed { CARET_RIGHT } else { CARET_DOWN })).sense(Sense::click()))
+                            .union(c.add(Label::new(&entry.pointer.pointer).sense(Sense::click())));
+                        if response.clicked() {
+                            toggled_pointer = Some(entry.pointer.pointer.clone());
+                        }
+                        Some(response)
+                    } else {
+                        Some(c.label(&entry.pointer.pointer))
+                    }
+                });
                 row.col(|ui, _| {
+                    if is_container {
+                        let summary = match entry.pointer.value_type {
+                            ValueType::Array(len) => format!("[{}]", len),
+                            _ => "{…}".to_string(),
+                        };
+                        return Some(ui.label(RichText::new(summary).weak()));
+                    }
                     let mut editing_index = self.editing_index.borrow_mut();
                     if editing_index.is_some() && editing_index.unwrap() == (row_index) {
                         let ref_mut = &mut *self.editing_value.borrow_mut();
@@ -118,6 +196,9 @@ impl ObjectTable {
                     }
                 });
             });
+            if let Some(pointer) = toggled_pointer {
+                self.toggle_collapsed(pointer);
+            }
             if let Some((updated_pointer, value)) = updated_value {
                 let editing_index = mem::take(&mut *self.editing_index.borrow_mut());
                 let row_index = editing_index.unwrap();
@@ -0,0 +1,165 @@
+use std::io::{BufRead, Read, Write};
+use std::path::Path;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Detect compression from first bytes of a file.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            Self::Gzip
+        } else if bytes.starts_with(&ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    /// Used when saving to a new file, compression is guessed from its extension.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()) {
+            Some(ext) if ext == "gz" => Self::Gzip,
+            Some(ext) if ext == "zst" || ext == "zstd" => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    pub fn is_extension(ext: &str) -> bool {
+        matches!(ext.to_lowercase().as_str(), "gz" | "zst" | "zstd")
+    }
+}
+
+pub fn decoder<'a, R: BufRead + 'a>(reader: R, compression: Compression) -> std::io::Result<Box<dyn Read + 'a>> {
+    match compression {
+        Compression::None => Ok(Box::new(reader)),
+        Compression::Gzip => Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader))),
+        #[cfg(not(target_arch = "wasm32"))]
+        Compression::Zstd => Ok(Box::new(zstd::stream::read::Decoder::with_buffer(reader)?)),
+        #[cfg(target_arch = "wasm32")]
+        Compression::Zstd => Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "zstd is not supported on web")),
+    }
+}
+
+/// Decompress in memory content, e.g. dropped file bytes.
+pub fn decompress(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let compression = Compression::detect(bytes);
+    let mut decompressed = Vec::with_capacity(bytes.len() * 4);
+    decoder(bytes, compression)?.read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+pub enum Encoder<W: Write> {
+    None(W),
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(not(target_arch = "wasm32"))]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+}
+
+impl<W: Write> Encoder<W> {
+    pub fn new(writer: W, compression: Compression) -> std::io::Result<Self> {
+        match compression {
+            Compression::None => Ok(Self::None(writer)),
+            Compression::Gzip => Ok(Self::Gzip(flate2::write::GzEncoder::new(writer, flate2::Compression::default()))),
+            #[cfg(not(target_arch = "wasm32"))]
+            Compression::Zstd => Ok(Self::Zstd(zstd::stream::write::Encoder::new(writer, zstd::DEFAULT_COMPRESSION_LEVEL)?)),
+            #[cfg(target_arch = "wasm32")]
+            Compression::Zstd => Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "zstd is not supported on web")),
+        }
+    }
+
+    /// Write compression trailer, then returns inner writer
+    pub fn finish(self) -> std::io::Result<W> {
+        match self {
+            Self::None(writer) => Ok(writer),
+            Self::Gzip(encoder) => encoder.finish(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Zstd(encoder) => encoder.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::None(writer) => writer.write(buf),
+            Self::Gzip(encoder) => encoder.write(buf),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::None(writer) => writer.flush(),
+            Self::Gzip(encoder) => encoder.flush(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &[u8] = br#"[{"a":1,"b":"x"},{"a":2,"b":null}]"#;
+
+    fn compress(bytes: &[u8], compression: Compression) -> Vec<u8> {
+        let mut encoder = Encoder::new(Vec::new(), compression).unwrap();
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn gzip_round_trip() {
+        let compressed = compress(JSON, Compression::Gzip);
+        assert_eq!(Compression::detect(&compressed), Compression::Gzip);
+        assert_eq!(decompress(&compressed).unwrap(), JSON);
+    }
+
+    #[test]
+    fn multi_member_gzip_is_read_entirely() {
+        let (first, second) = JSON.split_at(10);
+        let mut compressed = compress(first, Compression::Gzip);
+        compressed.extend(compress(second, Compression::Gzip));
+        assert_eq!(Compression::detect(&compressed), Compression::Gzip);
+        assert_eq!(decompress(&compressed).unwrap(), JSON);
+    }
+
+    #[test]
+    fn zstd_round_trip() {
+        let compressed = compress(JSON, Compression::Zstd);
+        assert_eq!(Compression::detect(&compressed), Compression::Zstd);
+        assert_eq!(decompress(&compressed).unwrap(), JSON);
+    }
+
+    #[test]
+    fn plain_content_is_kept_as_is() {
+        assert_eq!(compress(JSON, Compression::None), JSON);
+        assert_eq!(Compression::detect(JSON), Compression::None);
+        assert_eq!(Compression::detect(&[]), Compression::None);
+        assert_eq!(decompress(JSON).unwrap(), JSON);
+    }
+
+    #[test]
+    fn truncated_gzip_is_an_error() {
+        let compressed = compress(JSON, Compression::Gzip);
+        assert!(decompress(&compressed[..compressed.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn compression_from_extension() {
+        assert_eq!(Compression::from_path(Path::new("rows.json.GZ")), Compression::Gzip);
+        assert_eq!(Compression::from_path(Path::new("rows.yaml.zst")), Compression::Zstd);
+        assert_eq!(Compression::from_path(Path::new("rows.json")), Compression::None);
+    }
+}
@@ -0,0 +1,170 @@
+use std::path::Path;
+use crate::parser::compression::Compression;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FileFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl FileFormat {
+    pub const ALL: [FileFormat; 3] = [FileFormat::Json, FileFormat::Yaml, FileFormat::Toml];
+
+    /// Detect format from file extension, fallback on json. Compression extension is skipped: `.yaml.gz` is yaml.
+    pub fn from_path(path: &Path) -> Self {
+        let mut extension = path.extension().and_then(|ext| ext.to_str());
+        if extension.is_some_and(Compression::is_extension) {
+            extension = path.file_stem().map(Path::new).and_then(|stem| stem.extension()).and_then(|ext| ext.to_str());
+        }
+        match extension.map(|ext| ext.to_lowercase()) {
+            Some(ext) if ext == "yaml" || ext == "yml" => Self::Yaml,
+            Some(ext) if ext == "toml" => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+        }
+    }
+
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::Json => &["json"],
+            Self::Yaml => &["yaml", "yml"],
+            Self::Toml => &["toml"],
+        }
+    }
+}
+
+/// Convert yaml or toml content to json text, keeping keys order, so it can be parsed into flat representation.
+pub fn to_json(content: &str, format: FileFormat) -> Result<String, String> {
+    let value = match format {
+        FileFormat::Json => return Ok(content.to_string()),
+        FileFormat::Yaml => serde_yaml::from_str::<serde_json::Value>(content).map_err(|e| e.to_string())?,
+        FileFormat::Toml => toml_to_json_value(toml::from_str::<toml::Value>(content).map_err(|e| e.to_string())?),
+    };
+    serde_json::to_string(&value).map_err(|e| e.to_string())
+}
+
+/// Convert json text to yaml or toml, keeping keys order.
+pub fn from_json(json: &[u8], format: FileFormat) -> Result<Vec<u8>, String> {
+    if matches!(format, FileFormat::Json) {
+        return Ok(json.to_vec());
+    }
+    let value = serde_json::from_slice::<serde_json::Value>(json).map_err(|e| e.to_string())?;
+    match format {
+        FileFormat::Json => unreachable!(),
+        FileFormat::Yaml => serde_yaml::to_string(&value).map(|s| s.into_bytes()).map_err(|e| e.to_string()),
+        FileFormat::Toml => {
+            let Some(toml_value) = json_to_toml_value(value)? else {
+                return Err("toml document can't be null".to_string());
+            };
+            if !matches!(toml_value, toml::Value::Table(_)) {
+                return Err("toml document root has to be a table".to_string());
+            }
+            toml::to_string_pretty(&toml_value).map(|s| s.into_bytes()).map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn toml_to_json_value(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(s) => serde_json::Value::String(s),
+        toml::Value::Integer(i) => serde_json::Value::from(i),
+        toml::Value::Float(f) => serde_json::Value::from(f),
+        toml::Value::Boolean(b) => serde_json::Value::Bool(b),
+        toml::Value::Datetime(d) => serde_json::Value::String(d.to_string()),
+        toml::Value::Array(array) => serde_json::Value::Array(array.into_iter().map(toml_to_json_value).collect()),
+        toml::Value::Table(table) => serde_json::Value::Object(table.into_iter().map(|(k, v)| (k, toml_to_json_value(v))).collect()),
+    }
+}
+
+// toml has no null: null object members are omitted, null array elements are rejected.
+fn json_to_toml_value(value: serde_json::Value) -> Result<Option<toml::Value>, String> {
+    Ok(match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::Bool(b) => Some(toml::Value::Boolean(b)),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Some(toml::Value::Integer(i))
+            } else {
+                Some(toml::Value::Float(n.as_f64().unwrap_or_default()))
+            }
+        }
+        serde_json::Value::String(s) => Some(toml::Value::String(s)),
+        serde_json::Value::Array(array) => {
+            let mut toml_array = toml::value::Array::with_capacity(array.len());
+            for v in array {
+                toml_array.push(json_to_toml_value(v)?.ok_or_else(|| "toml array can't contain null".to_string())?);
+            }
+            Some(toml::Value::Array(toml_array))
+        }
+        serde_json::Value::Object(object) => {
+            let mut table = toml::value::Table::new();
+            for (k, v) in object {
+                if let Some(v) = json_to_toml_value(v)? {
+                    table.insert(k, v);
+                }
+            }
+            Some(toml::Value::Table(table))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_round_trip_keeps_keys_order() {
+        let json = r#"{"b":1,"a":[true,null,"x"],"c":{"d":1.5}}"#;
+        let yaml = from_json(json.as_bytes(), FileFormat::Yaml).unwrap();
+        assert_eq!(to_json(std::str::from_utf8(&yaml).unwrap(), FileFormat::Yaml).unwrap(), json);
+    }
+
+    #[test]
+    fn toml_round_trip_omits_null_members() {
+        let json = r#"{"b":1,"a":null,"c":{"d":"x"}}"#;
+        let toml = from_json(json.as_bytes(), FileFormat::Toml).unwrap();
+        assert_eq!(to_json(std::str::from_utf8(&toml).unwrap(), FileFormat::Toml).unwrap(), r#"{"b":1,"c":{"d":"x"}}"#);
+    }
+
+    #[test]
+    fn toml_rejects_null_in_array() {
+        assert_eq!(from_json(br#"{"a":[1,null]}"#, FileFormat::Toml), Err("toml array can't contain null".to_string()));
+    }
+
+    #[test]
+    fn toml_rejects_non_table_root() {
+        assert_eq!(from_json(b"[1,2]", FileFormat::Toml), Err("toml document root has to be a table".to_string()));
+        assert_eq!(from_json(b"null", FileFormat::Toml), Err("toml document can't be null".to_string()));
+    }
+
+    #[test]
+    fn toml_datetime_is_read_as_string() {
+        let json = to_json("date = 1979-05-27T07:32:00Z", FileFormat::Toml).unwrap();
+        assert_eq!(json, r#"{"date":"1979-05-27T07:32:00Z"}"#);
+        // Datetime is written back as a string, not as a toml datetime
+        let toml = from_json(json.as_bytes(), FileFormat::Toml).unwrap();
+        assert_eq!(std::str::from_utf8(&toml).unwrap().trim(), r#"date = "1979-05-27T07:32:00Z""#);
+    }
+
+    #[test]
+    fn invalid_content_is_an_error() {
+        assert!(to_json("a: [", FileFormat::Yaml).is_err());
+        assert!(to_json("a = ", FileFormat::Toml).is_err());
+        assert!(from_json(b"{", FileFormat::Yaml).is_err());
+    }
+
+    #[test]
+    fn json_is_kept_as_is() {
+        assert_eq!(to_json("{\"a\":1}", FileFormat::Json).unwrap(), "{\"a\":1}");
+        assert_eq!(from_json(b"{\"a\":1}", FileFormat::Json).unwrap(), b"{\"a\":1}".to_vec());
+    }
+}
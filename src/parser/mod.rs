@@ -8,10 +8,18 @@ use std::sync::{Arc, Mutex};
 
 
 use json_flat_parser::{FlatJsonValue, JsonArrayEntries, JSONParser, ParseOptions, ParseResult, PointerKey, ValueType};
-use rayon::iter::ParallelIterator;
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 use rayon::iter::IntoParallelIterator;
+use rayon::iter::IntoParallelRefIterator;
+use regex::{Regex, RegexBuilder};
 use rayon::prelude::{ParallelSliceMut};
 use crate::array_table::{Column, NON_NULL_FILTER_VALUE};
+use crate::parser::compression::{Compression, Encoder};
+use crate::parser::format::FileFormat;
+
+pub mod compression;
+pub mod format;
+
 #[macro_export]
 macro_rules! concat_string {
     () => { String::with_capacity(0) };
@@ -72,9 +80,11 @@ pub fn change_depth_array(previous_parse_result: ParseResult<String>, mut json_a
                         value_type: entry.pointer.value_type,
                         seen_count: 0,
                         order: unique_keys.len(),
+                        observed_types: vec![entry.pointer.value_type],
                     };
-                    if let Some(column) = unique_keys.iter_mut().find(|c| c.eq(&&column)) {
-                        column.seen_count += 1;
+                    if let Some(existing_column) = unique_keys.iter_mut().find(|c| c.eq(&&column)) {
+                        existing_column.seen_count += 1;
+                        existing_column.add_observed_type(column.value_type);
                     } else if !column.name.contains('#') {
                         unique_keys.push(column);
                     }
@@ -91,6 +101,9 @@ pub fn change_depth_array(previous_parse_result: ParseResult<String>, mut json_a
         for column_chunk in unique_keys_chunk {
             if let Some(column) = unique_keys.iter_mut().find(|c| c.eq(&&column_chunk)) {
                 column.seen_count += column_chunk.seen_count;
+                for value_type in column_chunk.observed_types {
+                    column.add_observed_type(value_type);
+                }
             } else if !column_chunk.name.contains('#') {
                 unique_keys.push(column_chunk);
             }
@@ -147,9 +160,11 @@ pub fn as_array(mut previous_parse_result: ParseResult<String>) -> Result<(Vec<J
                             value_type: entry.pointer.value_type,
                             seen_count: 1,
                             order: unique_keys.len(),
+                            observed_types: vec![entry.pointer.value_type],
                         };
                         if let Some(existing_column) = unique_keys.iter_mut().find(|c| c.eq(&&column)) {
                             existing_column.seen_count += 1;
+                            existing_column.add_observed_type(column.value_type);
                             if existing_column.value_type.eq(&ValueType::Null) {
                                 existing_column.value_type = column.value_type;
                             }
@@ -187,10 +202,28 @@ pub fn as_array(mut previous_parse_result: ParseResult<String>) -> Result<(Vec<J
 const LINE_ENDING: &'static [u8] = ",\r\n".as_bytes();
 #[cfg(not(windows))]
 const LINE_ENDING: &[u8] = ",\n".as_bytes();
-pub fn save_to_file(parent_pointer: &str, array: &Vec<JsonArrayEntries<String>>, file_path: &Path) -> std::io::Result<()> {
+pub fn save_to_file(parent_pointer: &str, array: &[JsonArrayEntries<String>], file_path: &Path, format: FileFormat, compression: Compression) -> std::io::Result<()> {
     // let start = crate::compatibility::now();
+    // Convert before creating file, which truncates it, so a failed conversion leaves file untouched
+    let converted = if matches!(format, FileFormat::Json) {
+        None
+    } else {
+        let mut json = Vec::with_capacity(array.len() * 64);
+        write_json(parent_pointer, array, &mut json)?;
+        Some(format::from_json(&json, format).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?)
+    };
     let file = fs::File::create(file_path)?;
-    let mut file = BufWriter::new(file);
+    let mut file = Encoder::new(BufWriter::new(file), compression)?;
+    match converted {
+        Some(converted) => file.write_all(&converted)?,
+        None => write_json(parent_pointer, array, &mut file)?,
+    }
+    file.finish()?.flush()?;
+    // println!("serialize and save file took {}ms", start.elapsed().as_millis());
+    Ok(())
+}
+
+pub fn write_json<W: Write>(parent_pointer: &str, array: &[JsonArrayEntries<String>], file: &mut W) -> std::io::Result<()> {
     if !parent_pointer.is_empty() {
         let split = parent_pointer.split('/');
         for frag in split {
@@ -229,8 +262,6 @@ pub fn save_to_file(parent_pointer: &str, array: &Vec<JsonArrayEntries<String>>,
             }
         }
     }
-    file.flush()?;
-    // println!("serialize and save file took {}ms", start.elapsed().as_millis());
     Ok(())
 }
 
@@ -254,7 +285,7 @@ pub fn filter_columns(previous_parse_result: &Vec<JsonArrayEntries<String>>, pre
                     should_add_row = false;
                     break;
                 }
-                if !filters_clone.is_empty() && (entry.value.as_ref().is_none() || !filters_clone.contains(entry.value.as_ref().unwrap())) {
+                if !filters_clone.is_empty() && (entry.value.as_ref().is_none() || !matches_filter_values(entry, &filters_clone)) {
                     should_add_row = false;
                     break;
                 }
@@ -270,20 +301,189 @@ pub fn filter_columns(previous_parse_result: &Vec<JsonArrayEntries<String>>, pre
     }
     res
 }
-pub fn search_occurrences(previous_parse_result: &[JsonArrayEntries<String>], term: &str) -> Vec<usize> {
-    let mut res: Vec<usize> = vec![];
-    for json_array_entry in previous_parse_result.iter() {
-        for entry in &json_array_entry.entries {
-            if !matches!(entry.pointer.value_type, ValueType::String) {
-                continue;
+/// Arrays match when they contain one of filtered values, other values when they equal one of them.
+fn matches_filter_values(entry: &FlatJsonValue<String>, filters: &[String]) -> bool {
+    let value = entry.value.as_ref().unwrap();
+    if matches!(entry.pointer.value_type, ValueType::Array(_)) {
+        if let Some(items) = primitive_array_items(value) {
+            return items.iter().any(|item| filters.contains(&primitive_text(item)));
+        }
+    }
+    filters.contains(value)
+}
+
+/// Elements of a serialized array when all of them are primitives (string, number, boolean or null).
+pub fn primitive_array_items(value: &str) -> Option<Vec<serde_json::Value>> {
+    match serde_json::from_str::<serde_json::Value>(value).ok()? {
+        serde_json::Value::Array(items) if items.iter().all(|item| !item.is_array() && !item.is_object()) => Some(items),
+        _ => None
+    }
+}
+
+/// Text of a primitive value as displayed in cells, strings without quotes.
+pub fn primitive_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(value) => value.clone(),
+        _ => value.to_string()
+    }
+}
+
+/// Json value of a flat entry value, strings being stored without quotes.
+pub fn json_value(value_type: ValueType, value: Option<&str>) -> serde_json::Value {
+    match (value_type, value) {
+        (_, None) | (ValueType::Null, _) => serde_json::Value::Null,
+        (ValueType::String, Some(value)) => serde_json::Value::String(value.to_string()),
+        (_, Some(value)) => serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string())),
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub case_sensitive: bool,
+    pub regex: bool,
+    pub match_keys: bool,
+    // Restrict search to this column
+    pub column: Option<String>,
+}
+
+pub fn search_regex(term: &str, options: &SearchOptions) -> Result<Regex, String> {
+    let pattern = if options.regex { term.to_string() } else { regex::escape(term) };
+    RegexBuilder::new(&pattern).case_insensitive(!options.case_sensitive).build().map_err(|e| e.to_string())
+}
+
+/// Returns position in `filtered_nodes` of rows having a string, number or boolean value (or a key) matching `regex`.
+pub fn search_occurrences(previous_parse_result: &[JsonArrayEntries<String>], filtered_nodes: &[usize], parent_pointer: &str,
+                          regex: &Regex, options: &SearchOptions) -> Vec<usize> {
+    filtered_nodes.par_iter().enumerate().filter_map(|(table_row_index, row_index)| {
+        let json_array_entry = &previous_parse_result[*row_index];
+        let prefix = concat_string!(parent_pointer, "/", json_array_entry.index.to_string());
+        let column_pointer = options.column.as_ref().map(|column| concat_string!(prefix, column));
+        let found = json_array_entry.entries.iter().any(|entry| {
+            if entry.pointer.pointer.len() <= prefix.len() || entry.pointer.pointer.ends_with("/#") {
+                return false;
             }
-            if let Some(ref value) = entry.value {
-                if value.to_lowercase().contains(term) {
-                    res.push(json_array_entry.index);
-                    break;
+            if let Some(ref column_pointer) = column_pointer {
+                if !entry.pointer.pointer.eq(column_pointer) {
+                    return false;
                 }
             }
-        }
+            if options.match_keys {
+                let key = entry.pointer.pointer.rsplit('/').next().unwrap_or_default();
+                if regex.is_match(key) {
+                    return true;
+                }
+            }
+            if !matches!(entry.pointer.value_type, ValueType::String | ValueType::Number | ValueType::Bool) {
+                return false;
+            }
+            entry.value.as_ref().is_some_and(|value| regex.is_match(value))
+        });
+        if found { Some(table_row_index) } else { None }
+    }).collect::<Vec<usize>>()
+}
+
+/// Type of a value typed or pasted by user: json numbers and booleans keep their type, anything else is a string.
+pub fn infer_value_type(value: &str) -> ValueType {
+    if value == "true" || value == "false" {
+        ValueType::Bool
+    } else if serde_json::from_str::<serde_json::Number>(value).is_ok() {
+        ValueType::Number
+    } else {
+        ValueType::String
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pointer: &str, value_type: ValueType, value: &str, index: usize) -> FlatJsonValue<String> {
+        FlatJsonValue { pointer: PointerKey { pointer: pointer.to_string(), value_type, depth: 2, index, position: 0 }, value: Some(value.to_string()) }
+    }
+
+    /// Rows of `[{"name": "Alice", "age": 30, "active": true}, {"name": "bob.smith", "age": 42, "active": false}, {"city": "Paris", "name": "a+b"}]`
+    fn rows() -> Vec<JsonArrayEntries<String>> {
+        vec![
+            JsonArrayEntries { entries: vec![
+                entry("/0/#", ValueType::Number, "0", 0),
+                entry("/0/name", ValueType::String, "Alice", 0),
+                entry("/0/age", ValueType::Number, "30", 0),
+                entry("/0/active", ValueType::Bool, "true", 0),
+            ], index: 0 },
+            JsonArrayEntries { entries: vec![
+                entry("/1/#", ValueType::Number, "1", 1),
+                entry("/1/name", ValueType::String, "bob.smith", 1),
+                entry("/1/age", ValueType::Number, "42", 1),
+                entry("/1/active", ValueType::Bool, "false", 1),
+            ], index: 1 },
+            JsonArrayEntries { entries: vec![
+                entry("/2/#", ValueType::Number, "2", 2),
+                entry("/2/city", ValueType::String, "Paris", 2),
+                entry("/2/name", ValueType::String, "a+b", 2),
+            ], index: 2 },
+        ]
+    }
+
+    fn search(term: &str, options: &SearchOptions) -> Vec<usize> {
+        let regex = search_regex(term, options).unwrap();
+        search_occurrences(&rows(), &[0, 1, 2], "", &regex, options)
+    }
+
+    #[test]
+    fn match_numbers_and_booleans() {
+        let options = SearchOptions::default();
+        assert_eq!(search("42", &options), vec![1]);
+        assert_eq!(search("true", &options), vec![0]);
+        assert_eq!(search("false", &options), vec![1]);
+        // Row number column is not searched
+        assert_eq!(search("2", &options), vec![1]);
+    }
+
+    #[test]
+    fn match_keys() {
+        let options = SearchOptions::default();
+        assert!(search("city", &options).is_empty());
+        let options = SearchOptions { match_keys: true, ..SearchOptions::default() };
+        assert_eq!(search("city", &options), vec![2]);
+        assert_eq!(search("name", &options), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn case_sensitivity() {
+        assert_eq!(search("alice", &SearchOptions::default()), vec![0]);
+        assert!(search("alice", &SearchOptions { case_sensitive: true, ..SearchOptions::default() }).is_empty());
+        assert_eq!(search("Alice", &SearchOptions { case_sensitive: true, ..SearchOptions::default() }), vec![0]);
+    }
+
+    #[test]
+    fn regex_and_literal_metacharacters() {
+        let options = SearchOptions { regex: true, ..SearchOptions::default() };
+        assert_eq!(search("^[ab]", &options), vec![0, 1, 2]);
+        assert_eq!(search("^\\d{2}$", &options), vec![0, 1]);
+        assert!(search_regex("(", &options).is_err());
+        let options = SearchOptions::default();
+        assert_eq!(search("a+b", &options), vec![2]);
+        assert_eq!(search(".", &options), vec![1]);
+        assert!(search_regex("(", &options).is_ok());
+    }
+
+    #[test]
+    fn column_scope() {
+        let options = SearchOptions { column: Some("/name".to_string()), ..SearchOptions::default() };
+        assert_eq!(search("a", &options), vec![0, 2]);
+        assert!(search("Paris", &options).is_empty());
+        let options = SearchOptions { column: Some("/city".to_string()), match_keys: true, ..SearchOptions::default() };
+        assert_eq!(search("city", &options), vec![2]);
+        assert!(search("name", &options).is_empty());
+    }
+
+    #[test]
+    fn positions_relative_to_filtered_rows() {
+        let options = SearchOptions::default();
+        let regex = search_regex("a", &options).unwrap();
+        assert_eq!(search_occurrences(&rows(), &[2, 0], "", &regex, &options), vec![0, 1]);
+        let regex = search_regex("bob", &options).unwrap();
+        assert_eq!(search_occurrences(&rows(), &[2, 1], "", &regex, &options), vec![1]);
+        assert!(search_occurrences(&rows(), &[0, 2], "", &regex, &options).is_empty());
     }
-    res
 }
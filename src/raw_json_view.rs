@@ -0,0 +1,176 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use egui::{Align, Button, Galley, Label, RichText, ScrollArea, Sense, TextEdit, TextStyle, Ui};
+
+use crate::components::icon;
+use crate::components::json_highlight::highlight_json;
+use crate::fonts::{CARET_DOWN, CARET_RIGHT};
+
+/// Pretty-printed json of a single row, folded by object or array, and editable as text.
+pub struct RawJsonView {
+    pub row_index: usize,
+    // Row json when view was built, view is built again when row changes
+    json: String,
+    lines: Vec<String>,
+    // Line index of closing bracket, by line index of opening bracket
+    fold_ends: Vec<Option<usize>>,
+    folded: BTreeSet<usize>,
+    visible_lines: Vec<usize>,
+    editing_text: Option<String>,
+    error: Option<String>,
+}
+
+impl RawJsonView {
+    pub fn new(row_index: usize, json: String) -> Self {
+        let pretty = serde_json::from_str::<serde_json::Value>(&json).ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok())
+            .unwrap_or_else(|| json.clone());
+        let lines = pretty.lines().map(|line| line.to_string()).collect::<Vec<String>>();
+        let mut fold_ends = vec![None; lines.len()];
+        let mut opened = vec![];
+        for (index, line) in lines.iter().enumerate() {
+            let line = line.trim_end();
+            if line.ends_with('{') || line.ends_with('[') {
+                opened.push(index);
+            } else if line.trim_start().starts_with(['}', ']']) {
+                if let Some(start) = opened.pop() {
+                    fold_ends[start] = Some(index);
+                }
+            }
+        }
+        let mut view = Self { row_index, json, lines, fold_ends, folded: BTreeSet::new(), visible_lines: vec![], editing_text: None, error: None };
+        view.refresh_visible_lines();
+        view
+    }
+
+    /// Build view again from row json, keeping folded lines when row is the same.
+    pub fn rebuild(&mut self, row_index: usize, json: String) {
+        let folded = if self.row_index == row_index { std::mem::take(&mut self.folded) } else { BTreeSet::new() };
+        *self = Self::new(row_index, json);
+        self.folded = folded.into_iter().filter(|line| self.fold_ends.get(*line).is_some_and(|end| end.is_some())).collect();
+        self.refresh_visible_lines();
+    }
+
+    pub fn is_outdated(&self, row_index: usize, json: &str) -> bool {
+        self.row_index != row_index || !self.json.eq(json)
+    }
+
+    #[inline]
+    pub fn is_editing(&self) -> bool {
+        self.editing_text.is_some()
+    }
+
+    /// Error of last apply, displayed along with text being edited until text is applied again.
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn stop_editing(&mut self) {
+        self.editing_text = None;
+    }
+
+    fn refresh_visible_lines(&mut self) {
+        let mut visible_lines = Vec::with_capacity(self.lines.len());
+        let mut index = 0;
+        while index < self.lines.len() {
+            visible_lines.push(index);
+            index = match self.fold_ends[index] {
+                Some(end) if self.folded.contains(&index) => end + 1,
+                _ => index + 1,
+            };
+        }
+        self.visible_lines = visible_lines;
+    }
+
+    fn set_folded(&mut self, folded: BTreeSet<usize>) {
+        self.folded = folded;
+        self.refresh_visible_lines();
+    }
+
+    /// Returns text to write in row when applied, text stays editable until `stop_editing` is called,
+    /// or `set_error` when it is not valid json.
+    pub fn ui(&mut self, ui: &mut Ui) -> Option<String> {
+        let mut applied_text = None;
+        ui.horizontal(|ui| {
+            if let Some(text) = self.editing_text.as_ref() {
+                if ui.button("Apply").clicked() {
+                    self.error = None;
+                    applied_text = Some(text.clone());
+                }
+                if ui.button("Cancel").clicked() {
+                    self.editing_text = None;
+                    self.error = None;
+                }
+            } else {
+                if ui.button("Edit").clicked() {
+                    self.editing_text = Some(self.lines.join("\n"));
+                }
+                ui.with_layout(egui::Layout::right_to_left(Align::Center), |ui| {
+                    if ui.add_enabled(!self.folded.is_empty(), Button::new("Unfold all")).clicked() {
+                        self.set_folded(BTreeSet::new());
+                    }
+                    if ui.button("Fold all").clicked() {
+                        // Top level object is kept unfolded, otherwise nothing would be displayed
+                        let folded = (1..self.lines.len()).filter(|line| self.fold_ends[*line].is_some()).collect();
+                        self.set_folded(folded);
+                    }
+                });
+            }
+        });
+        if let Some(error) = self.error.as_ref() {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        ui.separator();
+        if let Some(text) = self.editing_text.as_mut() {
+            let mut layouter = |ui: &Ui, text: &str, _wrap_width: f32| -> Arc<Galley> {
+                let job = highlight_json(ui.style(), text);
+                ui.fonts(|fonts| fonts.layout_job(job))
+            };
+            ScrollArea::both().auto_shrink([false, false]).show(ui, |ui| {
+                ui.add(TextEdit::multiline(text).code_editor().desired_width(f32::INFINITY).layouter(&mut layouter));
+            });
+        } else {
+            self.folded_text_ui(ui);
+        }
+        applied_text
+    }
+
+    fn folded_text_ui(&mut self, ui: &mut Ui) {
+        let mut toggled_line = None;
+        let line_height = ui.text_style_height(&TextStyle::Monospace);
+        ScrollArea::both().auto_shrink([false, false]).show_rows(ui, line_height, self.visible_lines.len(), |ui, range| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            for index in self.visible_lines[range].iter() {
+                ui.horizontal(|ui| {
+                    let line = &self.lines[*index];
+                    match self.fold_ends[*index] {
+                        Some(end) => {
+                            let folded = self.folded.contains(index);
+                            let caret = ui.add(Label::new(icon::icon(if folded { CARET_RIGHT } else { CARET_DOWN })).sense(Sense::click()));
+                            let text = if folded { format!("{} … {}", line, self.lines[end].trim_start()) } else { line.clone() };
+                            let text_response = ui.add(Label::new(highlight_json(ui.style(), &text)).sense(Sense::click()));
+                            if caret.clicked() || text_response.double_clicked() {
+                                toggled_line = Some(*index);
+                            }
+                        }
+                        None => {
+                            ui.add_space(ui.spacing().icon_width);
+                            ui.label(highlight_json(ui.style(), line));
+                        }
+                    }
+                });
+            }
+            if self.visible_lines.is_empty() {
+                ui.label(RichText::new("Empty row").weak());
+            }
+        });
+        if let Some(line) = toggled_line {
+            let mut folded = self.folded.clone();
+            if !folded.remove(&line) {
+                folded.insert(line);
+            }
+            self.set_folded(folded);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+
+use egui::Ui;
+use json_flat_parser::{FlatJsonValue, ParseOptions, PointerKey};
+
+use crate::object_table::ObjectTable;
+use crate::subtable_window::SubTable;
+use crate::View;
+
+/// A single row displayed as a vertical list of key/value pairs.
+pub struct RecordView {
+    pub row_index: usize,
+    root_pointer: String,
+    // Row json when view was built, view is built again when row changes
+    json: String,
+    table: ObjectTable,
+}
+
+impl RecordView {
+    pub fn new(row_index: usize, root: &PointerKey, json: String) -> Self {
+        let options = ParseOptions::default().parse_array(true).keep_object_raw_data(false)
+            .start_parse_at(root.pointer.clone()).start_depth(root.depth + 1).prefix(root.pointer.clone()).max_depth(10);
+        let result = SubTable::parse(&json, &options, true);
+        Self {
+            row_index,
+            root_pointer: root.pointer.clone(),
+            json,
+            table: ObjectTable::new(result.json).collapsible(),
+        }
+    }
+
+    /// Build view again from row json, keeping collapsed objects when row is the same.
+    pub fn rebuild(&mut self, row_index: usize, root: &PointerKey, json: String) {
+        let collapsed = if self.row_index == row_index { self.table.collapsed().clone() } else { HashSet::new() };
+        let view = Self::new(row_index, root, json);
+        *self = Self { table: view.table.with_collapsed(collapsed), ..view };
+    }
+
+    pub fn is_outdated(&self, row_index: usize, json: &str) -> bool {
+        self.row_index != row_index || !self.json.eq(json)
+    }
+
+    /// Returns edited value with its pointer relative to row.
+    pub fn ui(&mut self, ui: &mut Ui) -> Option<FlatJsonValue<String>> {
+        let mut edited_value = self.table.ui(ui).edited_value?;
+        edited_value.pointer.pointer = edited_value.pointer.pointer.strip_prefix(self.root_pointer.as_str())?.to_string();
+        Some(edited_value)
+    }
+}
@@ -0,0 +1,763 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use indexmap::{IndexMap, IndexSet};
+use json_flat_parser::{JsonArrayEntries, ValueType};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+
+use crate::array_table::Column;
+use crate::concat_string;
+
+pub const SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+// Strings having at most this number of distinct values are described with an enum
+const ENUM_MAX_VALUES: usize = 10;
+// Guard against $ref cycles
+const MAX_REF_RESOLUTION: usize = 32;
+// Keywords checked by validator
+const VALIDATED_KEYWORDS: [&str; 26] = ["type", "enum", "const", "minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum",
+    "minLength", "maxLength", "pattern", "required", "properties", "additionalProperties", "items", "minItems", "maxItems",
+    "uniqueItems", "allOf", "anyOf", "oneOf", "not", "$ref", "$defs", "definitions", "$schema", "$id"];
+// Keywords not affecting validation
+const ANNOTATION_KEYWORDS: [&str; 9] = ["title", "description", "default", "examples", "format", "$comment", "readOnly", "writeOnly", "deprecated"];
+
+#[derive(Default)]
+struct SchemaNode {
+    // Json schema type name along with its occurrences count
+    types: Vec<(&'static str, usize)>,
+    count: usize,
+    string_values: IndexSet<String>,
+    properties: IndexMap<String, SchemaNode>,
+    items: Option<Box<SchemaNode>>,
+}
+
+impl SchemaNode {
+    fn child(&mut self, segment: &str, is_array_index: bool) -> &mut SchemaNode {
+        if is_array_index {
+            self.items.get_or_insert_with(Default::default)
+        } else {
+            self.properties.entry(segment.to_string()).or_default()
+        }
+    }
+
+    fn add_value(&mut self, value_type: ValueType, value: Option<&String>) {
+        let type_name = match value_type {
+            ValueType::Array(_) => "array",
+            ValueType::Object(_) => "object",
+            ValueType::Number if value.is_some_and(|v| v.parse::<i64>().is_ok()) => "integer",
+            ValueType::Number => "number",
+            ValueType::String => "string",
+            ValueType::Bool => "boolean",
+            _ => "null",
+        };
+        self.count += 1;
+        if let Some(count) = self.types.iter_mut().find(|(name, _)| *name == type_name) {
+            count.1 += 1;
+        } else {
+            self.types.push((type_name, 1));
+        }
+        if type_name == "string" && self.string_values.len() <= ENUM_MAX_VALUES {
+            if let Some(value) = value {
+                self.string_values.insert(value.clone());
+            }
+        }
+    }
+
+    fn type_count(&self, type_name: &str) -> usize {
+        self.types.iter().find(|(name, _)| *name == type_name).map_or(0, |(_, count)| *count)
+    }
+
+    fn to_schema(&self) -> Value {
+        let mut schema = Map::new();
+        let mut types = self.types.iter().map(|(name, _)| *name).collect::<Vec<&str>>();
+        // integer is a subset of number
+        if types.contains(&"integer") && types.contains(&"number") {
+            types.retain(|name| *name != "integer");
+        }
+        match types.len() {
+            0 => {}
+            1 => { schema.insert("type".to_string(), json!(types[0])); }
+            _ => { schema.insert("type".to_string(), json!(types)); }
+        }
+        let string_count = self.type_count("string");
+        if string_count > 0 && self.string_values.len() <= ENUM_MAX_VALUES && string_count >= self.string_values.len() * 2 {
+            let mut values = self.string_values.iter().map(|value| json!(value)).collect::<Vec<Value>>();
+            if types.contains(&"null") {
+                values.push(Value::Null);
+            }
+            // enum restricts all values, so it is only used when strings are the only non null values
+            if types.iter().all(|name| *name == "string" || *name == "null") {
+                schema.insert("enum".to_string(), Value::Array(values));
+            }
+        }
+        if !self.properties.is_empty() {
+            let object_count = self.type_count("object");
+            let mut properties = Map::new();
+            let mut required = vec![];
+            for (name, property) in self.properties.iter() {
+                properties.insert(name.clone(), property.to_schema());
+                if property.count >= object_count {
+                    required.push(json!(name));
+                }
+            }
+            schema.insert("properties".to_string(), Value::Object(properties));
+            if !required.is_empty() {
+                schema.insert("required".to_string(), Value::Array(required));
+            }
+        }
+        if let Some(ref items) = self.items {
+            schema.insert("items".to_string(), items.to_schema());
+        }
+        Value::Object(schema)
+    }
+}
+
+/// Infer a json schema (draft 2020-12) describing the array rows, from key paths discovered in `columns`.
+/// A key is required when it is present in every occurrence of its parent object, type is a union when rows disagree,
+/// strings with few distinct values are described with an enum.
+pub fn infer_schema(parent_pointer: &str, nodes: &[JsonArrayEntries<String>], columns: &[Column]) -> Value {
+    let column_types = columns.iter().map(|c| (c.name.as_str(), c.value_type)).collect::<HashMap<&str, ValueType>>();
+    let mut root = SchemaNode::default();
+    // Create nodes in columns order first, so properties are ordered like table columns
+    for column in columns.iter() {
+        let mut node = &mut root;
+        for (key_prefix, segment) in segments(&column.name) {
+            let is_array_index = is_array_index(segment, column_types.get(key_prefix).copied());
+            node = node.child(segment, is_array_index);
+        }
+    }
+    for row in nodes.iter() {
+        root.add_value(ValueType::Object(true), None);
+        let prefix = concat_string!(parent_pointer, "/", row.index.to_string());
+        // Arrays of this row, numeric segment under an array is an index
+        let arrays = row.entries.iter()
+            .filter(|entry| matches!(entry.pointer.value_type, ValueType::Array(_)) && entry.pointer.pointer.len() > prefix.len())
+            .map(|entry| &entry.pointer.pointer[prefix.len()..])
+            .collect::<HashSet<&str>>();
+        for entry in row.entries.iter() {
+            if entry.pointer.pointer.len() <= prefix.len() || entry.pointer.pointer.ends_with("/#") {
+                continue;
+            }
+            let key = &entry.pointer.pointer[prefix.len()..];
+            let mut node = &mut root;
+            for (key_prefix, segment) in segments(key) {
+                let is_array_index = arrays.contains(key_prefix) && segment.parse::<usize>().is_ok();
+                node = node.child(segment, is_array_index);
+            }
+            node.add_value(entry.pointer.value_type, entry.value.as_ref());
+        }
+    }
+    json!({
+        "$schema": SCHEMA_DRAFT,
+        "type": "array",
+        "items": root.to_schema(),
+    })
+}
+
+fn is_array_index(segment: &str, parent_type: Option<ValueType>) -> bool {
+    matches!(parent_type, Some(ValueType::Array(_))) && segment.parse::<usize>().is_ok()
+}
+
+/// Segments of a key like `/a/b/0`, along with key prefix before each segment: ("", "a"), ("/a", "b"), ("/a/b", "0")
+fn segments(key: &str) -> impl Iterator<Item=(&str, &str)> {
+    key.match_indices('/').map(move |(i, _)| {
+        let end = key[i + 1..].find('/').map_or(key.len(), |j| i + 1 + j);
+        (&key[..i], &key[i + 1..end])
+    })
+}
+
+/// Schema violation of a row, `pointer` is relative to the row, e.g. `/address/city`, empty for the row itself.
+#[derive(Clone, Debug)]
+pub struct Violation {
+    pub pointer: String,
+    pub message: String,
+}
+
+impl Violation {
+    /// Whether violation is displayed in cell of `column`, violations of nested values are displayed in their parent column.
+    pub fn matches_column(&self, column: &str) -> bool {
+        if column.eq("/#") {
+            return self.pointer.is_empty();
+        }
+        !column.is_empty() && self.pointer.starts_with(column)
+            && (self.pointer.len() == column.len() || self.pointer.as_bytes()[column.len()] == b'/')
+    }
+}
+
+/// Validate rows against a subset of json schema (draft 2020-12) keywords:
+/// type, enum, const, numbers and strings bounds, pattern, required, properties, additionalProperties, items,
+/// minItems, maxItems, uniqueItems, allOf, anyOf, oneOf, not and local $ref.
+/// Other keywords (e.g. patternProperties, if, remote $ref) are ignored and listed by `ignored_keywords`.
+pub struct SchemaValidator {
+    schema: Value,
+    patterns: HashMap<String, Regex>,
+    ignored_keywords: BTreeSet<String>,
+}
+
+impl SchemaValidator {
+    pub fn new(schema: Value) -> Result<Self, String> {
+        if !schema.is_object() && !schema.is_boolean() {
+            return Err("JSON schema should be an object".to_string());
+        }
+        let mut patterns = HashMap::new();
+        Self::compile_patterns(&schema, &mut patterns)?;
+        let mut ignored_keywords = BTreeSet::new();
+        Self::collect_ignored_keywords(&schema, &mut ignored_keywords);
+        Ok(Self { schema, patterns, ignored_keywords })
+    }
+
+    pub fn schema(&self) -> &Value {
+        &self.schema
+    }
+
+    /// Keywords of schema which are not checked, rows are valid whatever these keywords say.
+    pub fn ignored_keywords(&self) -> &BTreeSet<String> {
+        &self.ignored_keywords
+    }
+
+    fn collect_ignored_keywords(schema: &Value, ignored_keywords: &mut BTreeSet<String>) {
+        let Value::Object(object) = schema else {
+            return;
+        };
+        for (keyword, value) in object.iter() {
+            match keyword.as_str() {
+                "properties" | "$defs" | "definitions" => {
+                    for sub_schema in value.as_object().into_iter().flat_map(|schemas| schemas.values()) {
+                        Self::collect_ignored_keywords(sub_schema, ignored_keywords);
+                    }
+                }
+                "allOf" | "anyOf" | "oneOf" => {
+                    for sub_schema in value.as_array().into_iter().flatten() {
+                        Self::collect_ignored_keywords(sub_schema, ignored_keywords);
+                    }
+                }
+                "items" | "additionalProperties" | "not" => Self::collect_ignored_keywords(value, ignored_keywords),
+                "$ref" if !value.as_str().is_some_and(|reference| reference.starts_with('#')) => {
+                    ignored_keywords.insert("$ref (not local)".to_string());
+                }
+                _ if VALIDATED_KEYWORDS.contains(&keyword.as_str()) || ANNOTATION_KEYWORDS.contains(&keyword.as_str()) => {}
+                _ => {
+                    ignored_keywords.insert(keyword.clone());
+                }
+            }
+        }
+    }
+
+    /// Schema rows are validated against: `items` when schema describes the whole array, otherwise schema itself.
+    pub fn row_schema(&self) -> &Value {
+        match self.schema.get("items") {
+            Some(items) if self.schema.get("type").and_then(Value::as_str) == Some("array") => items,
+            _ => &self.schema
+        }
+    }
+
+    /// Schema of value at `pointer` relative to row, e.g. `/address/city`, following properties and items.
+    pub fn property_schema(&self, pointer: &str) -> Option<&Value> {
+        let mut schema = self.resolve(self.row_schema());
+        for segment in pointer.split('/').skip(1) {
+            schema = match schema.get("properties").and_then(|properties| properties.get(segment)) {
+                Some(property) => property,
+                None if segment.parse::<usize>().is_ok() => schema.get("items")?,
+                None => return None,
+            };
+            schema = self.resolve(schema);
+        }
+        Some(schema)
+    }
+
+    pub fn validate_row(&self, row: &Value) -> Vec<Violation> {
+        let mut violations = vec![];
+        self.validate(self.row_schema(), row, "", &mut violations);
+        violations
+    }
+
+    fn compile_patterns(schema: &Value, patterns: &mut HashMap<String, Regex>) -> Result<(), String> {
+        match schema {
+            Value::Object(object) => {
+                if let Some(pattern) = object.get("pattern").and_then(Value::as_str) {
+                    let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+                    patterns.insert(pattern.to_string(), regex);
+                }
+                for value in object.values() {
+                    Self::compile_patterns(value, patterns)?;
+                }
+            }
+            Value::Array(array) => {
+                for value in array.iter() {
+                    Self::compile_patterns(value, patterns)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Follow local references like `#/$defs/address`.
+    pub fn resolve<'a>(&'a self, mut schema: &'a Value) -> &'a Value {
+        for _ in 0..MAX_REF_RESOLUTION {
+            match schema.get("$ref").and_then(Value::as_str).and_then(|reference| reference.strip_prefix('#')) {
+                Some(pointer) => match self.schema.pointer(pointer) {
+                    Some(referenced_schema) => schema = referenced_schema,
+                    None => break,
+                },
+                None => break,
+            }
+        }
+        schema
+    }
+
+    fn is_valid(&self, schema: &Value, instance: &Value) -> bool {
+        let mut violations = vec![];
+        self.validate(schema, instance, "", &mut violations);
+        violations.is_empty()
+    }
+
+    fn validate(&self, schema: &Value, instance: &Value, pointer: &str, violations: &mut Vec<Violation>) {
+        let mut violation = |pointer: &str, message: String| violations.push(Violation { pointer: pointer.to_string(), message });
+        let schema = match self.resolve(schema) {
+            Value::Bool(false) => {
+                violation(pointer, "value is not allowed".to_string());
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+        if let Some(types) = schema.get("type") {
+            let matches = match types {
+                Value::String(type_name) => type_matches(type_name, instance),
+                Value::Array(type_names) => type_names.iter().filter_map(Value::as_str).any(|type_name| type_matches(type_name, instance)),
+                _ => true,
+            };
+            if !matches {
+                violation(pointer, format!("expected {}, found {}", types_description(types), instance_type(instance)));
+                return;
+            }
+        }
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            if !values.contains(instance) {
+                violation(pointer, format!("value should be one of {}", values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")));
+            }
+        }
+        if let Some(value) = schema.get("const") {
+            if value != instance {
+                violation(pointer, format!("value should be {}", value));
+            }
+        }
+        match instance {
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64).filter(|minimum| number < *minimum) {
+                    violation(pointer, format!("value should be greater than or equal to {}", minimum));
+                }
+                if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64).filter(|maximum| number > *maximum) {
+                    violation(pointer, format!("value should be less than or equal to {}", maximum));
+                }
+                if let Some(minimum) = schema.get("exclusiveMinimum").and_then(Value::as_f64).filter(|minimum| number <= *minimum) {
+                    violation(pointer, format!("value should be greater than {}", minimum));
+                }
+                if let Some(maximum) = schema.get("exclusiveMaximum").and_then(Value::as_f64).filter(|maximum| number >= *maximum) {
+                    violation(pointer, format!("value should be less than {}", maximum));
+                }
+            }
+            Value::String(string) => {
+                let length = string.chars().count() as u64;
+                if let Some(min_length) = schema.get("minLength").and_then(Value::as_u64).filter(|min_length| length < *min_length) {
+                    violation(pointer, format!("value should have at least {} characters", min_length));
+                }
+                if let Some(max_length) = schema.get("maxLength").and_then(Value::as_u64).filter(|max_length| length > *max_length) {
+                    violation(pointer, format!("value should have at most {} characters", max_length));
+                }
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                    if self.patterns.get(pattern).is_some_and(|regex| !regex.is_match(string)) {
+                        violation(pointer, format!("value should match pattern {}", pattern));
+                    }
+                }
+            }
+            Value::Array(array) => {
+                if let Some(min_items) = schema.get("minItems").and_then(Value::as_u64).filter(|min_items| (array.len() as u64) < *min_items) {
+                    violation(pointer, format!("array should have at least {} items", min_items));
+                }
+                if let Some(max_items) = schema.get("maxItems").and_then(Value::as_u64).filter(|max_items| (array.len() as u64) > *max_items) {
+                    violation(pointer, format!("array should have at most {} items", max_items));
+                }
+                if schema.get("uniqueItems").and_then(Value::as_bool).unwrap_or(false)
+                    && array.iter().enumerate().any(|(i, item)| array[i + 1..].contains(item)) {
+                    violation(pointer, "array items should be unique".to_string());
+                }
+                if let Some(items) = schema.get("items") {
+                    for (i, item) in array.iter().enumerate() {
+                        self.validate(items, item, &concat_string!(pointer, "/", i.to_string()), violations);
+                    }
+                }
+            }
+            Value::Object(object) => {
+                if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                    for key in required.iter().filter_map(Value::as_str) {
+                        if !object.contains_key(key) {
+                            violations.push(Violation { pointer: concat_string!(pointer, "/", key), message: "required key is missing".to_string() });
+                        }
+                    }
+                }
+                let properties = schema.get("properties").and_then(Value::as_object);
+                for (key, value) in object.iter() {
+                    let key_pointer = concat_string!(pointer, "/", key);
+                    if let Some(property) = properties.and_then(|properties| properties.get(key)) {
+                        self.validate(property, value, &key_pointer, violations);
+                    } else {
+                        match schema.get("additionalProperties") {
+                            Some(Value::Bool(false)) => violations.push(Violation { pointer: key_pointer, message: "key is not allowed".to_string() }),
+                            Some(additional_properties) => self.validate(additional_properties, value, &key_pointer, violations),
+                            None => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+            for sub_schema in schemas.iter() {
+                self.validate(sub_schema, instance, pointer, violations);
+            }
+        }
+        if let Some(schemas) = schema.get("anyOf").and_then(Value::as_array) {
+            if !schemas.iter().any(|sub_schema| self.is_valid(sub_schema, instance)) {
+                violations.push(Violation { pointer: pointer.to_string(), message: "value does not match any of anyOf schemas".to_string() });
+            }
+        }
+        if let Some(schemas) = schema.get("oneOf").and_then(Value::as_array) {
+            let matching_count = schemas.iter().filter(|sub_schema| self.is_valid(sub_schema, instance)).count();
+            if matching_count != 1 {
+                violations.push(Violation { pointer: pointer.to_string(), message: format!("value should match exactly one of oneOf schemas, matches {}", matching_count) });
+            }
+        }
+        if let Some(sub_schema) = schema.get("not") {
+            if self.is_valid(sub_schema, instance) {
+                violations.push(Violation { pointer: pointer.to_string(), message: "value should not match schema".to_string() });
+            }
+        }
+    }
+}
+
+fn type_matches(type_name: &str, instance: &Value) -> bool {
+    match type_name {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "number" => instance.is_number(),
+        "integer" => instance.as_f64().is_some_and(|number| number.fract() == 0.0),
+        "string" => instance.is_string(),
+        _ => true,
+    }
+}
+
+fn types_description(types: &Value) -> String {
+    match types {
+        Value::Array(type_names) => type_names.iter().filter_map(Value::as_str).collect::<Vec<&str>>().join(" or "),
+        _ => types.as_str().unwrap_or_default().to_string(),
+    }
+}
+
+fn instance_type(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Editor used instead of a text field for values described by a schema.
+pub enum ValueEditor {
+    Enum(Vec<String>),
+    Date,
+    DateTime,
+}
+
+impl ValueEditor {
+    pub fn from_schema(schema: &Value) -> Option<Self> {
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            return Some(Self::Enum(values.iter().filter(|value| !value.is_null()).map(value_as_string).collect()));
+        }
+        match schema.get("format").and_then(Value::as_str) {
+            Some("date-time") => Some(Self::DateTime),
+            Some("date") => Some(Self::Date),
+            _ => None
+        }
+    }
+}
+
+/// Value as displayed in a cell: strings without quotes.
+pub fn value_as_string(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        _ => value.to_string()
+    }
+}
+
+/// Schema default value, used to pre-fill a key added to a row.
+pub fn default_value(schema: &Value) -> Option<String> {
+    schema.get("default").filter(|value| !value.is_null() && !value.is_object() && !value.is_array()).map(value_as_string)
+}
+
+/// Type of an edited value according to its schema: type of enum value it matches, otherwise first schema type it can be written as.
+pub fn value_type_for(schema: &Value, value: &str) -> Option<ValueType> {
+    if let Some(enum_value) = schema.get("enum").and_then(Value::as_array).and_then(|values| values.iter().find(|v| value_as_string(v).eq(value))) {
+        return match enum_value {
+            Value::Bool(_) => Some(ValueType::Bool),
+            Value::Number(_) => Some(ValueType::Number),
+            Value::String(_) => Some(ValueType::String),
+            _ => None,
+        };
+    }
+    let type_names = match schema.get("type") {
+        Some(Value::String(type_name)) => vec![type_name.as_str()],
+        Some(Value::Array(type_names)) => type_names.iter().filter_map(Value::as_str).collect(),
+        _ => return None,
+    };
+    type_names.into_iter().find_map(|type_name| match type_name {
+        "number" | "integer" if value.parse::<f64>().is_ok() => Some(ValueType::Number),
+        "boolean" if matches!(value, "true" | "false") => Some(ValueType::Bool),
+        "string" => Some(ValueType::String),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use json_flat_parser::{FlatJsonValue, PointerKey};
+
+    use super::*;
+
+    fn entry(pointer: String, value_type: ValueType, value: Option<&str>) -> FlatJsonValue<String> {
+        let depth = pointer.matches('/').count() as u8;
+        FlatJsonValue { pointer: PointerKey { pointer, value_type, depth, index: 0, position: 0 }, value: value.map(str::to_string) }
+    }
+
+    /// Row at `index` of array at root, with `values` keys relative to row.
+    fn row(index: usize, values: &[(&str, ValueType, Option<&str>)]) -> JsonArrayEntries<String> {
+        let prefix = concat_string!("/", index.to_string());
+        let mut entries = vec![entry(concat_string!(prefix, "/#"), ValueType::Number, Some(&index.to_string()))];
+        entries.extend(values.iter().map(|(key, value_type, value)| entry(concat_string!(prefix, key), *value_type, *value)));
+        entries.push(entry(prefix, ValueType::Object(true), None));
+        JsonArrayEntries { entries, index }
+    }
+
+    fn column(name: &str, value_type: ValueType) -> Column {
+        Column { name: name.to_string(), depth: name.matches('/').count() as u8, value_type, seen_count: 0, order: 0, observed_types: vec![value_type] }
+    }
+
+    fn items_schema(rows: &[JsonArrayEntries<String>], columns: &[Column]) -> Value {
+        infer_schema("", rows, columns)["items"].clone()
+    }
+
+    #[test]
+    fn infer_enum_of_repeated_strings() {
+        let rows = ["a", "b", "a", "b", "a"].iter().enumerate()
+            .map(|(i, status)| row(i, &[("/status", ValueType::String, Some(status))])).collect::<Vec<_>>();
+        let schema = items_schema(&rows, &[column("/status", ValueType::String)]);
+        assert_eq!(schema["properties"]["status"], json!({"type": "string", "enum": ["a", "b"]}));
+    }
+
+    #[test]
+    fn infer_enum_with_null() {
+        let rows = [Some("a"), Some("a"), None, Some("a")].iter().enumerate()
+            .map(|(i, status)| match status {
+                Some(status) => row(i, &[("/status", ValueType::String, Some(status))]),
+                None => row(i, &[("/status", ValueType::Null, None)]),
+            }).collect::<Vec<_>>();
+        let schema = items_schema(&rows, &[column("/status", ValueType::String)]);
+        assert_eq!(schema["properties"]["status"], json!({"type": ["string", "null"], "enum": ["a", null]}));
+    }
+
+    #[test]
+    fn infer_no_enum_for_distinct_strings() {
+        let rows = ["a", "b", "c"].iter().enumerate()
+            .map(|(i, name)| row(i, &[("/name", ValueType::String, Some(name))])).collect::<Vec<_>>();
+        let schema = items_schema(&rows, &[column("/name", ValueType::String)]);
+        assert_eq!(schema["properties"]["name"], json!({"type": "string"}));
+    }
+
+    #[test]
+    fn infer_required_across_heterogeneous_rows() {
+        let rows = vec![
+            row(0, &[("/id", ValueType::Number, Some("1")), ("/name", ValueType::String, Some("x")),
+                ("/address", ValueType::Object(true), None), ("/address/city", ValueType::String, Some("Paris"))]),
+            row(1, &[("/id", ValueType::Number, Some("2")), ("/address", ValueType::Object(true), None),
+                ("/address/city", ValueType::String, Some("Lyon")), ("/address/zip", ValueType::String, Some("69000"))]),
+            row(2, &[("/id", ValueType::Number, Some("3"))]),
+        ];
+        let columns = [column("/id", ValueType::Number), column("/name", ValueType::String), column("/address", ValueType::Object(true)),
+            column("/address/city", ValueType::String), column("/address/zip", ValueType::String)];
+        let schema = items_schema(&rows, &columns);
+        assert_eq!(schema["required"], json!(["id"]));
+        assert_eq!(schema["properties"].as_object().unwrap().keys().collect::<Vec<_>>(), vec!["id", "name", "address"]);
+        // city is in every address object, even though address is missing from a row
+        assert_eq!(schema["properties"]["address"]["required"], json!(["city"]));
+    }
+
+    #[test]
+    fn infer_type_unions() {
+        let rows = vec![
+            row(0, &[("/a", ValueType::Number, Some("1")), ("/b", ValueType::Number, Some("1"))]),
+            row(1, &[("/a", ValueType::Number, Some("1.5")), ("/b", ValueType::String, Some("x"))]),
+            row(2, &[("/a", ValueType::Number, Some("2")), ("/b", ValueType::Bool, Some("true"))]),
+        ];
+        let schema = items_schema(&rows, &[column("/a", ValueType::Number), column("/b", ValueType::Number)]);
+        // integer is a subset of number
+        assert_eq!(schema["properties"]["a"]["type"], json!("number"));
+        assert_eq!(schema["properties"]["b"]["type"], json!(["integer", "string", "boolean"]));
+        // enum is only used when strings are the only non null values
+        assert!(schema["properties"]["b"].get("enum").is_none());
+    }
+
+    #[test]
+    fn infer_array_items() {
+        let rows = vec![
+            row(0, &[("/tags", ValueType::Array(2), None), ("/tags/0", ValueType::String, Some("x")), ("/tags/1", ValueType::String, Some("y"))]),
+            row(1, &[("/tags", ValueType::Array(1), None), ("/tags/0", ValueType::String, Some("z"))]),
+        ];
+        let schema = infer_schema("", &rows, &[column("/tags", ValueType::Array(2))]);
+        assert_eq!(schema["$schema"], json!(SCHEMA_DRAFT));
+        assert_eq!(schema["type"], json!("array"));
+        assert_eq!(schema["items"]["properties"]["tags"], json!({"type": "array", "items": {"type": "string"}}));
+    }
+
+    fn violations(schema: Value, row: Value) -> Vec<(String, String)> {
+        SchemaValidator::new(schema).unwrap().validate_row(&row).into_iter().map(|v| (v.pointer, v.message)).collect()
+    }
+
+    fn pointers(schema: Value, row: Value) -> Vec<String> {
+        violations(schema, row).into_iter().map(|(pointer, _)| pointer).collect()
+    }
+
+    #[test]
+    fn type_and_union_type() {
+        assert_eq!(violations(json!({"type": "string"}), json!(1)), vec![(String::new(), "expected string, found number".to_string())]);
+        assert!(pointers(json!({"type": ["string", "null"]}), json!(null)).is_empty());
+        assert_eq!(violations(json!({"type": ["string", "null"]}), json!(true)), vec![(String::new(), "expected string or null, found boolean".to_string())]);
+        assert!(pointers(json!({"type": "integer"}), json!(2.0)).is_empty());
+        assert_eq!(pointers(json!({"type": "integer"}), json!(2.5)), vec![""]);
+    }
+
+    #[test]
+    fn type_mismatch_skips_other_keywords() {
+        assert_eq!(pointers(json!({"type": "string", "minLength": 2, "enum": ["ab"]}), json!(1)).len(), 1);
+    }
+
+    #[test]
+    fn enum_and_const() {
+        assert!(pointers(json!({"enum": ["a", 1, null]}), json!(1)).is_empty());
+        assert_eq!(violations(json!({"enum": ["a", 1]}), json!("b")), vec![(String::new(), "value should be one of \"a\", 1".to_string())]);
+        assert!(pointers(json!({"const": {"a": 1}}), json!({"a": 1})).is_empty());
+        assert_eq!(pointers(json!({"const": "a"}), json!("b")), vec![""]);
+    }
+
+    #[test]
+    fn number_bounds() {
+        let schema = json!({"minimum": 1, "maximum": 10});
+        assert!(pointers(schema.clone(), json!(1)).is_empty());
+        assert!(pointers(schema.clone(), json!(10)).is_empty());
+        assert_eq!(violations(schema.clone(), json!(0)), vec![(String::new(), "value should be greater than or equal to 1".to_string())]);
+        assert_eq!(violations(schema, json!(10.5)), vec![(String::new(), "value should be less than or equal to 10".to_string())]);
+        let schema = json!({"exclusiveMinimum": 1, "exclusiveMaximum": 10});
+        assert_eq!(pointers(schema.clone(), json!(1)), vec![""]);
+        assert_eq!(pointers(schema.clone(), json!(10)), vec![""]);
+        assert!(pointers(schema, json!(5)).is_empty());
+    }
+
+    #[test]
+    fn string_length_and_pattern() {
+        let schema = json!({"minLength": 2, "maxLength": 3, "pattern": "^[a-zé]+$"});
+        assert!(pointers(schema.clone(), json!("éé")).is_empty());
+        assert_eq!(violations(schema.clone(), json!("a")), vec![(String::new(), "value should have at least 2 characters".to_string())]);
+        assert_eq!(violations(schema.clone(), json!("abcd")), vec![(String::new(), "value should have at most 3 characters".to_string())]);
+        assert_eq!(violations(schema, json!("AB")), vec![(String::new(), "value should match pattern ^[a-zé]+$".to_string())]);
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        assert!(SchemaValidator::new(json!({"properties": {"a": {"pattern": "("}}})).is_err());
+        assert!(SchemaValidator::new(json!(1)).is_err());
+    }
+
+    #[test]
+    fn array_keywords() {
+        let schema = json!({"minItems": 1, "maxItems": 2, "uniqueItems": true, "items": {"type": "number"}});
+        assert!(pointers(schema.clone(), json!([1, 2])).is_empty());
+        assert_eq!(violations(schema.clone(), json!([])), vec![(String::new(), "array should have at least 1 items".to_string())]);
+        assert_eq!(violations(schema.clone(), json!([1, 2, 3])), vec![(String::new(), "array should have at most 2 items".to_string())]);
+        assert_eq!(violations(schema.clone(), json!([1, 1])), vec![(String::new(), "array items should be unique".to_string())]);
+        assert_eq!(pointers(schema, json!([1, "a"])), vec!["/1"]);
+    }
+
+    #[test]
+    fn object_keywords() {
+        let schema = json!({"required": ["a", "b"], "properties": {"a": {"type": "string"}}, "additionalProperties": false});
+        assert_eq!(violations(schema.clone(), json!({"a": "x", "c": 1})), vec![
+            ("/b".to_string(), "required key is missing".to_string()),
+            ("/c".to_string(), "key is not allowed".to_string()),
+        ]);
+        assert_eq!(pointers(json!({"additionalProperties": {"type": "number"}}), json!({"a": 1, "b": "x"})), vec!["/b"]);
+    }
+
+    #[test]
+    fn nested_pointers() {
+        let schema = json!({
+            "properties": {
+                "address": {"properties": {"city": {"type": "string"}}, "required": ["zip"]},
+                "tags": {"items": {"properties": {"name": {"minLength": 1}}}}
+            }
+        });
+        let row = json!({"address": {"city": 1}, "tags": [{"name": "a"}, {"name": ""}]});
+        assert_eq!(pointers(schema, row), vec!["/address/zip", "/address/city", "/tags/1/name"]);
+    }
+
+    #[test]
+    fn combinators() {
+        let any_of = json!({"anyOf": [{"type": "string"}, {"type": "number"}]});
+        assert!(pointers(any_of.clone(), json!(1)).is_empty());
+        assert_eq!(violations(any_of, json!(true)), vec![(String::new(), "value does not match any of anyOf schemas".to_string())]);
+        let one_of = json!({"oneOf": [{"type": "number"}, {"type": "integer"}]});
+        assert!(pointers(one_of.clone(), json!(1.5)).is_empty());
+        assert_eq!(violations(one_of, json!(1)), vec![(String::new(), "value should match exactly one of oneOf schemas, matches 2".to_string())]);
+        let all_of = json!({"properties": {"a": {"allOf": [{"minimum": 1}, {"maximum": 2}]}}});
+        assert_eq!(pointers(all_of.clone(), json!({"a": 3})), vec!["/a"]);
+        assert!(pointers(all_of, json!({"a": 2})).is_empty());
+        assert_eq!(pointers(json!({"not": {"type": "null"}}), json!(null)), vec![""]);
+        assert_eq!(pointers(json!(false), json!(1)), vec![""]);
+    }
+
+    #[test]
+    fn local_ref() {
+        let schema = json!({
+            "$defs": {"address": {"properties": {"city": {"type": "string"}}}},
+            "properties": {"home": {"$ref": "#/$defs/address"}}
+        });
+        assert_eq!(pointers(schema.clone(), json!({"home": {"city": 1}})), vec!["/home/city"]);
+        let validator = SchemaValidator::new(schema).unwrap();
+        assert_eq!(validator.property_schema("/home/city"), Some(&json!({"type": "string"})));
+        // Cycle is not followed forever
+        assert!(pointers(json!({"$defs": {"a": {"$ref": "#/$defs/a"}}, "$ref": "#/$defs/a"}), json!(1)).is_empty());
+    }
+
+    #[test]
+    fn array_schema_validates_items() {
+        let schema = json!({"type": "array", "items": {"properties": {"a": {"type": "string"}}}});
+        assert_eq!(pointers(schema, json!({"a": 1})), vec!["/a"]);
+    }
+
+    #[test]
+    fn unsupported_keywords_are_listed() {
+        let validator = SchemaValidator::new(json!({
+            "$schema": SCHEMA_DRAFT,
+            "title": "row",
+            "properties": {"if": {"type": "string", "format": "date", "multipleOf": 2}, "b": {"$ref": "other.json"}},
+            "patternProperties": {"^x": {}},
+            "items": {"oneOf": [{"contains": {}}]}
+        })).unwrap();
+        assert_eq!(validator.ignored_keywords().iter().map(String::as_str).collect::<Vec<&str>>(),
+                   vec!["$ref (not local)", "contains", "multipleOf", "patternProperties"]);
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use egui::{Context, Ui};
 use json_flat_parser::{FlatJsonValue, ParseOptions, ParseResult, PointerKey, ValueType};
 use json_flat_parser::lexer::Lexer;
@@ -41,8 +43,8 @@ impl SubTable {
         }
     }
 
-    fn parse(content: &String, options: &ParseOptions, state_seen_start_parse_at: bool) -> ParseResult<String> {
-        let mut lexer = Lexer::new(content.as_str().as_bytes());
+    pub(crate) fn parse(content: &str, options: &ParseOptions, state_seen_start_parse_at: bool) -> ParseResult<String> {
+        let mut lexer = Lexer::new(content.as_bytes());
         let mut parser = Parser::new(&mut lexer);
         parser.state_seen_start_parse_at = state_seen_start_parse_at;
         let result = parser.parse(options, options.start_depth).unwrap().to_owned();
@@ -59,11 +61,11 @@ impl SubTable {
 
     pub fn update_nodes(&mut self, pointer: PointerKey, value: Option<String>) {
         if let Some(ref mut array_table) = self.array_table {
-            if let Some(entry) = array_table.nodes[self.row_index].entries.iter_mut()
+            if let Some(entry) = Arc::make_mut(&mut array_table.nodes)[self.row_index].entries.iter_mut()
                 .find(|entry| entry.pointer.pointer.eq(&pointer.pointer)) {
                 entry.value = value;
             } else {
-                array_table.nodes[self.row_index].entries.push(FlatJsonValue::<String>{ pointer, value});
+                Arc::make_mut(&mut array_table.nodes)[self.row_index].entries.push(FlatJsonValue::<String>{ pointer, value});
             }
         } else {
             let table = self.object_table.as_mut().unwrap();
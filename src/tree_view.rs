@@ -0,0 +1,212 @@
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::rc::Rc;
+
+use egui::{Key, Label, RichText, ScrollArea, Sense, TextEdit, Ui};
+use json_flat_parser::{FlatJsonValue, JsonArrayEntries, JSONParser, ParseOptions, ValueType};
+
+use crate::components::icon;
+use crate::components::json_highlight::highlight_json;
+use crate::fonts::{CARET_DOWN, CARET_RIGHT};
+use crate::{concat_string, log};
+
+/// Children of large arrays are displayed by page
+const PAGE_SIZE: usize = 100;
+
+pub enum TreeAction {
+    /// Node selected, `pointer` being relative to row
+    Select { row_index: usize, pointer: String },
+    /// Leaf value edited, `pointer` being relative to row
+    Edit { row_index: usize, pointer: String, value_type: ValueType, value: Option<String> },
+}
+
+/// Collapsible tree of the document, built from flat entries of rows.
+pub struct TreeView {
+    root_pointer: String,
+    expanded: HashSet<String>,
+    // Count of children displayed, by pointer of expanded arrays
+    shown_children: HashMap<String, usize>,
+    // Children of expanded objects and arrays, by container pointer, kept until rows change
+    children: HashMap<String, Rc<Vec<FlatJsonValue<String>>>>,
+    selected: Option<String>,
+    editing: Option<(String, String)>,
+    // Text field takes focus on the frame editing starts only, so leaving it applies edit
+    focus_editing: bool,
+}
+
+impl TreeView {
+    pub fn new(root_pointer: String) -> Self {
+        let mut expanded = HashSet::new();
+        expanded.insert(root_pointer.clone());
+        Self {
+            root_pointer,
+            expanded,
+            shown_children: HashMap::new(),
+            children: HashMap::new(),
+            selected: None,
+            editing: None,
+            focus_editing: false,
+        }
+    }
+
+    /// Children are outdated once rows changed.
+    pub fn invalidate(&mut self) {
+        self.children.clear();
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, rows: &[JsonArrayEntries<String>]) -> Option<TreeAction> {
+        let mut action = None;
+        ScrollArea::both().auto_shrink([false, false]).show(ui, |ui| {
+            let root_pointer = self.root_pointer.clone();
+            let key = if root_pointer.is_empty() { "root" } else { root_pointer.as_str() };
+            if !self.container_line(ui, 0, &root_pointer, key, format!("[{}]", rows.len())) {
+                return;
+            }
+            let shown = self.shown_count(&root_pointer, rows.len());
+            for (row_index, row) in rows.iter().enumerate().take(shown) {
+                if let Some(root) = row.entries.last() {
+                    self.node_ui(ui, 1, row_index, &row.entries, root, &mut action);
+                }
+            }
+            self.show_more_line(ui, 1, &root_pointer, rows.len());
+        });
+        action
+    }
+
+    #[inline]
+    fn shown_count(&self, pointer: &str, count: usize) -> usize {
+        self.shown_children.get(pointer).copied().unwrap_or(PAGE_SIZE).min(count)
+    }
+
+    /// Returns whether container is expanded.
+    fn container_line(&mut self, ui: &mut Ui, indent: usize, pointer: &str, key: &str, summary: String) -> bool {
+        let expanded = self.expanded.contains(pointer);
+        let mut toggled = false;
+        ui.horizontal(|ui| {
+            ui.add_space(indent as f32 * ui.spacing().indent);
+            let caret = ui.add(Label::new(icon::icon(if expanded { CARET_DOWN } else { CARET_RIGHT })).sense(Sense::click()));
+            let label = ui.selectable_label(self.selected.as_deref() == Some(pointer), key);
+            ui.label(RichText::new(summary).weak());
+            toggled = caret.clicked() || label.double_clicked();
+            if label.clicked() {
+                self.selected = Some(pointer.to_string());
+            }
+        });
+        if toggled && !self.expanded.remove(pointer) {
+            self.expanded.insert(pointer.to_string());
+        }
+        expanded
+    }
+
+    fn show_more_line(&mut self, ui: &mut Ui, indent: usize, pointer: &str, count: usize) {
+        let shown = self.shown_count(pointer, count);
+        if shown >= count {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.add_space(indent as f32 * ui.spacing().indent + ui.spacing().icon_width);
+            if ui.small_button(format!("Show {} more of {}", PAGE_SIZE.min(count - shown), count - shown)).clicked() {
+                self.shown_children.insert(pointer.to_string(), shown + PAGE_SIZE);
+            }
+        });
+    }
+
+    /// Row root is the last entry of `entries`.
+    fn node_ui(&mut self, ui: &mut Ui, indent: usize, row_index: usize, entries: &[FlatJsonValue<String>],
+               entry: &FlatJsonValue<String>, action: &mut Option<TreeAction>) {
+        let row_root = entries.last().map_or("", |root| root.pointer.pointer.as_str());
+        let pointer = entry.pointer.pointer.as_str();
+        let key = pointer.rsplit('/').next().unwrap_or(pointer);
+        let relative_pointer = || pointer.strip_prefix(row_root).unwrap_or(pointer).to_string();
+        let was_selected = self.selected.as_deref() == Some(pointer);
+        match entry.pointer.value_type {
+            ValueType::Array(_) | ValueType::Object(_) => {
+                let summary = match entry.pointer.value_type {
+                    ValueType::Array(len) => format!("[{}]", len),
+                    _ => "{…}".to_string(),
+                };
+                let expanded = self.container_line(ui, indent, pointer, key, summary);
+                if !was_selected && self.selected.as_deref() == Some(pointer) {
+                    *action = Some(TreeAction::Select { row_index, pointer: relative_pointer() });
+                }
+                if !expanded {
+                    return;
+                }
+                let children = self.children(entries, entry, row_root);
+                let shown = if matches!(entry.pointer.value_type, ValueType::Array(_)) { self.shown_count(pointer, children.len()) } else { children.len() };
+                for child in children.iter().take(shown) {
+                    self.node_ui(ui, indent + 1, row_index, entries, child, action);
+                }
+                self.show_more_line(ui, indent + 1, pointer, children.len());
+            }
+            _ => {
+                ui.horizontal(|ui| {
+                    ui.add_space(indent as f32 * ui.spacing().indent + ui.spacing().icon_width);
+                    if ui.selectable_label(was_selected, key).clicked() {
+                        self.selected = Some(pointer.to_string());
+                        *action = Some(TreeAction::Select { row_index, pointer: relative_pointer() });
+                    }
+                    match self.editing.as_mut() {
+                        Some((editing_pointer, value)) if editing_pointer == pointer => {
+                            let response = ui.add(TextEdit::singleline(value));
+                            if mem::take(&mut self.focus_editing) {
+                                response.request_focus();
+                            }
+                            if ui.input(|i| i.key_pressed(Key::Escape)) {
+                                self.editing = None;
+                            } else if response.lost_focus() || ui.input(|i| i.key_pressed(Key::Enter)) {
+                                let value = mem::take(value);
+                                self.editing = None;
+                                if !entry.value.as_deref().unwrap_or("").eq(&value) {
+                                    // An emptied string stays an empty string, other values become null
+                                    let value = if value.is_empty() && !matches!(entry.pointer.value_type, ValueType::String) { None } else { Some(value) };
+                                    *action = Some(TreeAction::Edit { row_index, pointer: relative_pointer(), value_type: entry.pointer.value_type, value });
+                                }
+                            }
+                        }
+                        _ => {
+                            let text = match (entry.pointer.value_type, entry.value.as_ref()) {
+                                (_, None) => "null".to_string(),
+                                (ValueType::String, Some(value)) => serde_json::Value::String(value.clone()).to_string(),
+                                (_, Some(value)) => value.clone(),
+                            };
+                            let response = ui.add(Label::new(highlight_json(ui.style(), &text)).truncate(true).sense(Sense::click()))
+                                .on_hover_text("Double click to edit");
+                            if response.double_clicked() {
+                                self.editing = Some((pointer.to_string(), entry.value.clone().unwrap_or_default()));
+                                self.focus_editing = true;
+                            }
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    /// Children flattened in row entries, or parsed from raw json of container when it was not flattened.
+    fn children(&mut self, entries: &[FlatJsonValue<String>], parent: &FlatJsonValue<String>, row_root: &str) -> Rc<Vec<FlatJsonValue<String>>> {
+        if let Some(children) = self.children.get(&parent.pointer.pointer) {
+            return children.clone();
+        }
+        let prefix = concat_string!(parent.pointer.pointer, "/");
+        let row_number_pointer = concat_string!(row_root, "/#");
+        let is_child = |entry: &FlatJsonValue<String>| entry.pointer.depth == parent.pointer.depth + 1 && entry.pointer.pointer.starts_with(&prefix);
+        let mut children = entries.iter().filter(|entry| is_child(entry) && !entry.pointer.pointer.eq(&row_number_pointer)).cloned().collect::<Vec<FlatJsonValue<String>>>();
+        if children.is_empty() {
+            if let Some(raw) = parent.value.as_ref() {
+                let options = ParseOptions::default().prefix(parent.pointer.pointer.clone())
+                    .start_depth(parent.pointer.depth + 1).parse_array(true).max_depth(parent.pointer.depth + 1);
+                children = match JSONParser::parse(raw.as_str(), options) {
+                    Ok(result) => result.to_owned().json.into_iter().filter(|entry| is_child(entry)).collect(),
+                    Err(e) => {
+                        log!("Failed to parse {}: {}", parent.pointer.pointer, e);
+                        vec![]
+                    }
+                };
+            }
+        }
+        let children = Rc::new(children);
+        self.children.insert(parent.pointer.pointer.clone(), children.clone());
+        children
+    }
+}
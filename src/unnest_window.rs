@@ -0,0 +1,146 @@
+use egui::{Context, Id, RichText};
+use json_flat_parser::{FlatJsonValue, JSONParser, ParseOptions, ValueType};
+use serde_json::Value;
+
+use crate::array_table::ArrayTable;
+use crate::{concat_string, View};
+
+/// Value edited in unnested view, to be written in source table.
+pub struct UnnestedEdit {
+    /// Row index in source table
+    pub row_index: usize,
+    /// Pointer of edited value, relative to source row
+    pub pointer: String,
+    pub value_type: ValueType,
+    pub value: Option<String>,
+}
+
+/// Derived view having one row per element of an array column, other columns of source row being repeated.
+pub struct UnnestWindow {
+    column: String,
+    // Columns unnested from source table to this view, e.g. `/orders > /orders/items`
+    path: String,
+    depth: u8,
+    table: ArrayTable,
+    // Row index in source table and element index in unnested array, by unnested row index
+    origins: Vec<(usize, usize)>,
+    // Entries of rows when view was built, to find edited values
+    snapshot: Vec<Vec<FlatJsonValue<String>>>,
+}
+
+impl UnnestWindow {
+    /// `rows` are source rows index along with their json, rows without element in `column` are skipped.
+    pub fn new(column: String, path: String, rows: Vec<(usize, &str)>, depth: u8) -> Result<Self, String> {
+        let mut unnested_rows = vec![];
+        let mut origins = vec![];
+        for (row_index, json) in rows {
+            let row = serde_json::from_str::<Value>(json).map_err(|e| e.to_string())?;
+            let Some(Value::Array(elements)) = row.pointer(&column) else {
+                continue;
+            };
+            for (element_index, element) in elements.iter().enumerate() {
+                let mut unnested_row = row.clone();
+                if let Some(value) = unnested_row.pointer_mut(&column) {
+                    *value = element.clone();
+                }
+                unnested_rows.push(unnested_row);
+                origins.push((row_index, element_index));
+            }
+        }
+        if unnested_rows.is_empty() {
+            return Err(format!("{} has no element to unnest", column));
+        }
+        let json = Value::Array(unnested_rows).to_string();
+        let result = JSONParser::parse(json.as_str(), ParseOptions::default().parse_array(false).max_depth(depth))
+            .map_err(|e| e.to_string())?.to_owned();
+        let parse_result = result.clone_except_json();
+        let (nodes, columns) = crate::parser::as_array(result)?;
+        let mut table = ArrayTable::new(Some(parse_result), nodes, columns, depth, String::new());
+        table.is_sub_table = true;
+        table.unnest_path = Some(path.clone());
+        let snapshot = table.nodes.iter().map(|row| row.entries.clone()).collect();
+        Ok(Self { column, path, depth, table, origins, snapshot })
+    }
+
+    /// Build view again from source rows, keeping filters, pinned columns and scroll position.
+    pub fn rebuild(&mut self, rows: Vec<(usize, &str)>) -> Result<(), String> {
+        let state = self.table.view_state();
+        *self = Self::new(self.column.clone(), self.path.clone(), rows, self.depth)?;
+        self.table.restore_view_state(state);
+        Ok(())
+    }
+
+    /// Replace unnested rows of changed source rows, view is built again from `rows` when their count of elements changed.
+    pub fn refresh_rows(&mut self, changed_rows: Vec<(usize, &str)>, rows: Vec<(usize, &str)>) -> Result<(), String> {
+        for (row_index, json) in changed_rows {
+            let row = serde_json::from_str::<Value>(json).map_err(|e| e.to_string())?;
+            let elements = match row.pointer(&self.column) {
+                Some(Value::Array(elements)) => elements.clone(),
+                _ => vec![],
+            };
+            // Unnested rows are in source row order then element order
+            let positions = self.origins.iter().enumerate()
+                .filter(|(_, (origin_row, _))| *origin_row == row_index)
+                .map(|(position, _)| position).collect::<Vec<usize>>();
+            if positions.len() != elements.len() {
+                return self.rebuild(rows);
+            }
+            for (position, element) in positions.into_iter().zip(elements) {
+                let mut unnested_row = row.clone();
+                if let Some(value) = unnested_row.pointer_mut(&self.column) {
+                    *value = element;
+                }
+                self.table.replace_row(position, unnested_row.to_string())?;
+                self.snapshot[position] = self.table.nodes[position].entries.clone();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    pub fn show(&mut self, ctx: &Context, open: &mut bool) -> Vec<UnnestedEdit> {
+        let mut edited = false;
+        egui::Window::new(format!("Unnest {}", self.path))
+            .id(Id::new("unnest_window").with(&self.path))
+            .open(open)
+            .default_size([640.0, 400.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(RichText::new(format!("{} rows, one per element of {}", self.origins.len(), self.column)).weak());
+                edited = self.table.ui(ui).edited_value.is_some();
+            });
+        if edited {
+            self.edits()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Values edited in view since it was built, with their pointer in source row.
+    fn edits(&self) -> Vec<UnnestedEdit> {
+        let mut edits = vec![];
+        for (position, row) in self.table.nodes.iter().enumerate() {
+            let (row_index, element_index) = self.origins[row.index];
+            let prefix = concat_string!("/", row.index.to_string());
+            let snapshot = &self.snapshot[position];
+            for entry in row.entries.iter() {
+                let Some(relative_pointer) = entry.pointer.pointer.get(prefix.len()..).filter(|pointer| !pointer.is_empty() && !pointer.eq(&"/#")) else {
+                    continue;
+                };
+                let previous = snapshot.iter().find(|previous| previous.pointer.pointer.eq(&entry.pointer.pointer));
+                if previous.is_some_and(|previous| previous.value.eq(&entry.value) && previous.pointer.value_type == entry.pointer.value_type) {
+                    continue;
+                }
+                let pointer = match relative_pointer.strip_prefix(self.column.as_str()) {
+                    Some(rest) if rest.is_empty() || rest.starts_with('/') => concat_string!(self.column, "/", element_index.to_string(), rest),
+                    _ => relative_pointer.to_string()
+                };
+                edits.push(UnnestedEdit { row_index, pointer, value_type: entry.pointer.value_type, value: entry.value.clone() });
+            }
+        }
+        edits
+    }
+}